@@ -0,0 +1,155 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use crate::config::DailyScryConfig;
+use crate::error::{Error, Result};
+
+use async_trait::async_trait;
+use log::{debug, warn};
+use scryfall::Card;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// The delay between two requests of an export, if `DAILY_SCRY_REQUEST_DELAY_MS` is not set
+///
+/// Scryfall asks for 50 to 100 milliseconds between requests.
+const EXPORT_REQUEST_DELAY_MS: usize = 100;
+
+/// Parses a file of scryfall or oracle ids, one per line
+///
+/// Empty lines and lines starting with `#` are skipped.
+///
+/// # Arguments
+///
+/// * `content` - The content of the file
+pub fn parse_ids(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_owned())
+        .collect()
+}
+
+#[async_trait(?Send)]
+pub trait CardLookup {
+    async fn lookup(&self, id: &str) -> Result<Card>;
+}
+
+/// Looks a card up by its scryfall id and falls back to its oracle id
+pub struct ScryfallCardLookup();
+
+#[async_trait(?Send)]
+impl CardLookup for ScryfallCardLookup {
+    async fn lookup(&self, id: &str) -> Result<Card> {
+        let uuid: Uuid = id
+            .parse()
+            .map_err(|_| Error::InvalidCardId { id: id.to_owned() })?;
+        match Card::scryfall_id(uuid).await {
+            Ok(card) => Ok(card),
+            Err(_) => Ok(Card::search_random(&format!("oracleid:{}", uuid)).await?),
+        }
+    }
+}
+
+/// Looks up every id and returns the cards with the id they were found by
+///
+/// Ids that don't resolve are logged and skipped. Between two requests the
+/// `DAILY_SCRY_REQUEST_DELAY_MS` or 100 milliseconds are waited.
+///
+/// # Arguments
+///
+/// * `config` - The [`DailyScryConfig`] holding the request delay
+/// * `lookup` - The [`CardLookup`] the cards are looked up with
+/// * `ids` - The ids as returned by [`parse_ids`]
+pub async fn lookup_cards<L: CardLookup>(
+    config: &DailyScryConfig,
+    lookup: L,
+    ids: &[String],
+) -> Vec<(String, Card)> {
+    let delay_ms = config.request_delay_ms.unwrap_or(EXPORT_REQUEST_DELAY_MS);
+    let mut cards = vec![];
+    for (index, id) in ids.iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(Duration::from_millis(delay_ms as u64)).await;
+        }
+        match lookup.lookup(id).await {
+            Ok(card) => {
+                debug!("found {} for {}", card.name, id);
+                cards.push((id.clone(), card));
+            }
+            Err(error) => warn!("skipping {}: {}", id, error),
+        }
+    }
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+
+    #[test]
+    fn test_parse_ids() {
+        let content = "# Alpha\n\
+        ddaa0be1-7358-4ea2-8c40-be6d699a6631\n\
+        \n\
+          56719f6a-1a6c-4c0a-8d21-18f7d7350b68  ";
+        assert_eq!(
+            parse_ids(content),
+            vec![
+                "ddaa0be1-7358-4ea2-8c40-be6d699a6631",
+                "56719f6a-1a6c-4c0a-8d21-18f7d7350b68",
+            ]
+        );
+    }
+
+    struct TestCardLookup {
+        card: Card,
+    }
+
+    #[async_trait(?Send)]
+    impl CardLookup for TestCardLookup {
+        async fn lookup(&self, id: &str) -> Result<Card> {
+            if id == "unknown" {
+                return Err(Error::InvalidCardId { id: id.to_owned() });
+            }
+            let mut card = self.card.clone();
+            card.name = id.to_owned();
+            Ok(card)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_cards_skips_unknown_ids() {
+        let mut config = DailyScryConfig::default();
+        config.request_delay_ms = Some(0);
+        let lookup = TestCardLookup {
+            card: card_fixture("grizzly_bears"),
+        };
+        let ids = vec![
+            "Grizzly Bears".to_owned(),
+            "unknown".to_owned(),
+            "Fireball".to_owned(),
+        ];
+
+        let cards = lookup_cards(&config, lookup, &ids).await;
+
+        assert_eq!(
+            cards
+                .iter()
+                .map(|(id, card)| (id.as_str(), card.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("Grizzly Bears", "Grizzly Bears"), ("Fireball", "Fireball")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scryfall_card_lookup_invalid_id() {
+        let result = ScryfallCardLookup().lookup("not an id").await;
+        assert!(matches!(result, Err(Error::InvalidCardId { .. })));
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+use crate::format::split_artists;
+
+#[derive(Clone)]
+pub struct ArtistFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for ArtistFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!(
+            "allowed artists: {:?}, excluded artists: {:?}",
+            config.allowed_artists, config.excluded_artists
+        )
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let artists = artists(&card);
+
+        if !config.allowed_artists.is_empty()
+            && !artists
+                .iter()
+                .any(|artist| config.allowed_artists.contains(artist))
+        {
+            return false;
+        }
+
+        return !artists
+            .iter()
+            .any(|artist| config.excluded_artists.contains(artist));
+    }
+}
+
+/// Returns the lowercased artists of the card and its faces
+///
+/// Cards illustrated by multiple artists list them separated by `&`.
+fn artists(card: &Card) -> Vec<String> {
+    let face_artists = card
+        .card_faces
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|face| face.artist);
+    card.artist
+        .clone()
+        .into_iter()
+        .chain(face_artists)
+        .flat_map(|artist| {
+            split_artists(&artist)
+                .into_iter()
+                .map(|artist| artist.to_lowercase())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
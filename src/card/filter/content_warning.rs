@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: MIT
  */
 
+use async_trait::async_trait;
 use scryfall::Card;
 
 use crate::card::filter::CardFilter;
@@ -12,8 +13,9 @@ use crate::config::DailyScryConfig;
 #[derive(Clone)]
 pub struct ContentWarningFilter {}
 
+#[async_trait(?Send)]
 impl CardFilter for ContentWarningFilter {
-    fn filter(&self, _: &DailyScryConfig, card: Card) -> bool {
+    async fn filter(&self, _: &DailyScryConfig, card: Card) -> bool {
         return !card.content_warning;
     }
 }
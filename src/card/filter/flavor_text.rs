@@ -0,0 +1,37 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+#[derive(Clone)]
+pub struct HasFlavorTextFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for HasFlavorTextFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!("require flavor text: {}", config.require_flavor_text)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        if !config.require_flavor_text {
+            return true;
+        }
+
+        if card.flavor_text.is_some() {
+            return true;
+        }
+
+        return card
+            .card_faces
+            .unwrap_or_default()
+            .iter()
+            .any(|face| face.flavor_text.is_some());
+    }
+}
@@ -0,0 +1,33 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::card::BorderColor;
+use scryfall::set::SetType;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+/// Skips cards of un-sets like Unglued or Unfinity and other silver-bordered cards, if
+/// `DAILY_SCRY_EXCLUDE_FUNNY` is set
+#[derive(Clone)]
+pub struct FunnyFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for FunnyFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!("exclude funny: {}", config.exclude_funny)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        if !config.exclude_funny {
+            return true;
+        }
+
+        card.set_type != SetType::Funny && card.border_color != BorderColor::Silver
+    }
+}
@@ -0,0 +1,26 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+/// Skips cards whose image is not a high resolution scan yet, like placeholders of new spoilers
+#[derive(Clone)]
+pub struct HighresImageFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for HighresImageFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!("require highres: {}", config.require_highres)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        return !config.require_highres || card.highres_image;
+    }
+}
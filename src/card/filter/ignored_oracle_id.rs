@@ -4,6 +4,8 @@
  * SPDX-License-Identifier: MIT
  */
 
+use async_trait::async_trait;
+use log::warn;
 use scryfall::Card;
 
 use crate::card::filter::CardFilter;
@@ -12,13 +14,22 @@ use crate::config::DailyScryConfig;
 #[derive(Clone)]
 pub struct IgnoredOracleIdFilter {}
 
+#[async_trait(?Send)]
 impl CardFilter for IgnoredOracleIdFilter {
-    fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!(
+            "ignored oracle ids: {}, allow no oracle id: {}",
+            config.ignored_oracle_ids.clone().unwrap_or_default().len(),
+            config.allow_no_oracle_id
+        )
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
         let oracle_id_option = card.oracle_id.clone();
 
         if oracle_id_option.is_none() {
-            println!("card has no oracle_id url: {:?}", card.clone().scryfall_uri);
-            return true;
+            warn!("card has no oracle_id url: {:?}", card.clone().scryfall_uri);
+            return config.allow_no_oracle_id;
         }
 
         let oracle_id = oracle_id_option.unwrap();
@@ -6,14 +6,22 @@
 
 use std::any::type_name;
 
+use async_trait::async_trait;
+
 use scryfall::Card;
 
 use crate::config::DailyScryConfig;
 
+#[async_trait(?Send)]
 pub trait CardFilter {
     fn name(&self) -> &'static str {
         return type_name::<Self>().split("::").last().unwrap();
     }
 
-    fn filter(&self, config: &DailyScryConfig, card: Card) -> bool;
+    /// Describes the configuration the filter uses, e.g. for `--list-filters`
+    fn parameters(&self, _: &DailyScryConfig) -> String {
+        String::new()
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool;
 }
@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+/// Skips cards with one of the keywords in `DAILY_SCRY_EXCLUDE_KEYWORDS`
+///
+/// Only the keywords scryfall lists for the card (e.g. `flying` or `mill`) are checked. If
+/// `DAILY_SCRY_EXCLUDE_KEYWORDS_IN_ORACLE_TEXT` is set, cards whose oracle text contains one of
+/// them are skipped as well, which also catches phrases like `coin flip`. As this is a plain
+/// substring match, `mill` also excludes cards mentioning a `windmill`.
+#[derive(Clone)]
+pub struct KeywordExclusionFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for KeywordExclusionFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!(
+            "excluded keywords: {:?}, in oracle text: {}",
+            config.excluded_keywords, config.exclude_keywords_in_oracle_text
+        )
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let has_excluded_keyword = card
+            .keywords
+            .iter()
+            .any(|keyword| config.excluded_keywords.contains(&keyword.to_lowercase()));
+        if has_excluded_keyword {
+            return false;
+        }
+
+        if !config.exclude_keywords_in_oracle_text {
+            return true;
+        }
+
+        let oracle_text = oracle_text(&card);
+        return !config
+            .excluded_keywords
+            .iter()
+            .any(|keyword| oracle_text.contains(keyword));
+    }
+}
+
+/// Returns the lowercased oracle text of the card and its faces
+fn oracle_text(card: &Card) -> String {
+    let face_texts = card
+        .card_faces
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|face| face.oracle_text);
+    card.oracle_text
+        .clone()
+        .into_iter()
+        .chain(face_texts)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase()
+}
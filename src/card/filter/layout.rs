@@ -0,0 +1,35 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+#[derive(Clone)]
+pub struct LayoutFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for LayoutFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        let allowed_layouts = config.allowed_layouts.clone().unwrap_or_default();
+        if allowed_layouts.is_empty() {
+            return "allowed layouts: all".to_owned();
+        }
+        format!("allowed layouts: {:?}", allowed_layouts)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let allowed_layouts = config.allowed_layouts.clone().unwrap_or_default();
+
+        if allowed_layouts.is_empty() {
+            return true;
+        }
+
+        return allowed_layouts.contains(&card.layout);
+    }
+}
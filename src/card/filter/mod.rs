@@ -4,10 +4,218 @@
  * SPDX-License-Identifier: MIT
  */
 
+mod artist;
 mod content_warning;
+mod flavor_text;
+mod funny;
+mod highres_image;
 mod ignored_oracle_id;
 mod interface;
+mod keyword;
+mod layout;
+mod oracle_length;
+mod power_toughness;
+mod printings;
+mod set_type;
+mod type_line;
 
+pub use crate::card::filter::artist::ArtistFilter;
 pub use crate::card::filter::content_warning::ContentWarningFilter;
+pub use crate::card::filter::flavor_text::HasFlavorTextFilter;
+pub use crate::card::filter::funny::FunnyFilter;
+pub use crate::card::filter::highres_image::HighresImageFilter;
 pub use crate::card::filter::ignored_oracle_id::IgnoredOracleIdFilter;
 pub use crate::card::filter::interface::CardFilter;
+pub use crate::card::filter::keyword::KeywordExclusionFilter;
+pub use crate::card::filter::layout::LayoutFilter;
+pub use crate::card::filter::oracle_length::OracleLengthFilter;
+pub use crate::card::filter::power_toughness::PowerToughnessFilter;
+pub use crate::card::filter::printings::{PrintingsFilter, ScryfallPrintsCounter};
+pub use crate::card::filter::set_type::SetTypeFilter;
+pub use crate::card::filter::type_line::TypeFilter;
+
+use crate::config::DailyScryConfig;
+
+/// Returns the card filters whose configuration is active, in the order they are applied
+///
+/// # Arguments
+///
+/// * `config` - The [`DailyScryConfig`] the filters are configured with
+pub fn build_filters(config: &DailyScryConfig) -> Vec<Box<dyn CardFilter>> {
+    let mut filters: Vec<Box<dyn CardFilter>> = vec![];
+
+    let has_ignored_oracle_ids = config
+        .ignored_oracle_ids
+        .as_ref()
+        .is_some_and(|ids| !ids.is_empty());
+    if has_ignored_oracle_ids || !config.allow_no_oracle_id {
+        filters.push(Box::new(IgnoredOracleIdFilter {}));
+    }
+
+    filters.push(Box::new(ContentWarningFilter {}));
+
+    if config
+        .allowed_layouts
+        .as_ref()
+        .is_some_and(|layouts| !layouts.is_empty())
+    {
+        filters.push(Box::new(LayoutFilter {}));
+    }
+
+    if config.exclude_funny {
+        filters.push(Box::new(FunnyFilter {}));
+    }
+
+    if config
+        .allowed_set_types
+        .as_ref()
+        .is_some_and(|set_types| !set_types.is_empty())
+    {
+        filters.push(Box::new(SetTypeFilter {}));
+    }
+
+    if !config.excluded_types.is_empty() {
+        filters.push(Box::new(TypeFilter {}));
+    }
+
+    if config.require_flavor_text {
+        filters.push(Box::new(HasFlavorTextFilter {}));
+    }
+
+    if config.require_highres {
+        filters.push(Box::new(HighresImageFilter {}));
+    }
+
+    if config.min_oracle_len.is_some() || config.max_oracle_len.is_some() {
+        filters.push(Box::new(OracleLengthFilter {}));
+    }
+
+    if config.min_power.is_some()
+        || config.max_power.is_some()
+        || config.min_toughness.is_some()
+        || config.max_toughness.is_some()
+    {
+        filters.push(Box::new(PowerToughnessFilter {}));
+    }
+
+    if !config.allowed_artists.is_empty() || !config.excluded_artists.is_empty() {
+        filters.push(Box::new(ArtistFilter {}));
+    }
+
+    if !config.excluded_keywords.is_empty() {
+        filters.push(Box::new(KeywordExclusionFilter {}));
+    }
+
+    // filters that need to call scryfall come last, so they only run for otherwise valid cards
+    if config
+        .min_printings
+        .is_some_and(|min_printings| min_printings > 1)
+    {
+        filters.push(Box::new(PrintingsFilter::new(ScryfallPrintsCounter())));
+    }
+
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_names(config: &DailyScryConfig) -> Vec<&'static str> {
+        build_filters(config)
+            .iter()
+            .map(|card_filter| card_filter.name())
+            .collect()
+    }
+
+    fn default_config() -> DailyScryConfig {
+        DailyScryConfig::default()
+    }
+
+    #[test]
+    fn test_default_filters() {
+        assert_eq!(
+            filter_names(&default_config()),
+            vec!["ContentWarningFilter"]
+        );
+    }
+
+    #[test]
+    fn test_ignored_oracle_id_filter() {
+        let mut config = default_config();
+        config.ignored_oracle_ids = Some(vec!["56719f6a-1a6c-4c0a-8d21-18f7d7350b68"
+            .parse()
+            .unwrap()]);
+        assert_eq!(
+            filter_names(&config),
+            vec!["IgnoredOracleIdFilter", "ContentWarningFilter"]
+        );
+
+        let mut config = default_config();
+        config.allow_no_oracle_id = false;
+        assert_eq!(
+            filter_names(&config),
+            vec!["IgnoredOracleIdFilter", "ContentWarningFilter"]
+        );
+    }
+
+    #[test]
+    fn test_all_filters() {
+        let mut config = default_config();
+        config.allow_no_oracle_id = false;
+        config.allowed_layouts = Some(vec![scryfall::card::Layout::Normal]);
+        config.exclude_funny = true;
+        config.allowed_set_types = Some(vec![scryfall::set::SetType::Expansion]);
+        config.excluded_types = vec!["basic".to_owned()];
+        config.require_flavor_text = true;
+        config.require_highres = true;
+        config.max_oracle_len = Some(100);
+        config.min_power = Some(5);
+        config.excluded_artists = vec!["rebecca guay".to_owned()];
+        config.excluded_keywords = vec!["flying".to_owned()];
+        config.min_printings = Some(5);
+        assert_eq!(
+            filter_names(&config),
+            vec![
+                "IgnoredOracleIdFilter",
+                "ContentWarningFilter",
+                "LayoutFilter",
+                "FunnyFilter",
+                "SetTypeFilter",
+                "TypeFilter",
+                "HasFlavorTextFilter",
+                "HighresImageFilter",
+                "OracleLengthFilter",
+                "PowerToughnessFilter",
+                "ArtistFilter",
+                "KeywordExclusionFilter",
+                "PrintingsFilter"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_printings_of_one_is_inactive() {
+        let mut config = default_config();
+        config.min_printings = Some(1);
+        assert_eq!(filter_names(&config), vec!["ContentWarningFilter"]);
+    }
+
+    #[test]
+    fn test_preset_filters() {
+        for preset in ["spells_only", "permanents_only"] {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_PRESET", Some(preset)),
+                    ("DAILY_SCRY_ALLOWED_LAYOUTS", None),
+                    ("DAILY_SCRY_EXCLUDED_TYPES", None),
+                ],
+                || {
+                    let names = filter_names(&DailyScryConfig::new());
+                    assert!(names.contains(&"LayoutFilter"));
+                    assert!(names.contains(&"TypeFilter"));
+                },
+            );
+        }
+    }
+}
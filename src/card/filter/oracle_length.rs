@@ -0,0 +1,45 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+#[derive(Clone)]
+pub struct OracleLengthFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for OracleLengthFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!(
+            "min oracle length: {:?}, max oracle length: {:?}",
+            config.min_oracle_len, config.max_oracle_len
+        )
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let length = oracle_text(&card).chars().count();
+
+        if config.min_oracle_len.is_some_and(|min| length < min) {
+            return false;
+        }
+
+        return !config.max_oracle_len.is_some_and(|max| length > max);
+    }
+}
+
+fn oracle_text(card: &Card) -> String {
+    return match &card.card_faces {
+        Some(faces) => faces
+            .iter()
+            .map(|face| face.oracle_text.clone().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => card.oracle_text.clone().unwrap_or_default(),
+    };
+}
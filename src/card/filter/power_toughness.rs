@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+/// Skips creatures whose power or toughness lies outside `DAILY_SCRY_MIN_POWER`,
+/// `DAILY_SCRY_MAX_POWER`, `DAILY_SCRY_MIN_TOUGHNESS` and `DAILY_SCRY_MAX_TOUGHNESS`
+///
+/// Cards without power and toughness pass, unless `DAILY_SCRY_POWER_TOUGHNESS_CREATURES_ONLY` is
+/// set. Values like `*` or `1+*` can't be compared and only pass if
+/// `DAILY_SCRY_INCLUDE_VARIABLE_POWER_TOUGHNESS` is set. Cards with multiple faces pass if any
+/// face is within the range.
+#[derive(Clone)]
+pub struct PowerToughnessFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for PowerToughnessFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!(
+            "power: {:?}..{:?}, toughness: {:?}..{:?}, creatures only: {}, include variable: {}",
+            config.min_power,
+            config.max_power,
+            config.min_toughness,
+            config.max_toughness,
+            config.power_toughness_creatures_only,
+            config.include_variable_power_toughness
+        )
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let stats = power_and_toughness(&card);
+        if stats.is_empty() {
+            return !config.power_toughness_creatures_only;
+        }
+
+        stats.iter().any(
+            |(power, toughness)| match (parse_stat(power), parse_stat(toughness)) {
+                (Some(power), Some(toughness)) => {
+                    in_range(power, config.min_power, config.max_power)
+                        && in_range(toughness, config.min_toughness, config.max_toughness)
+                }
+                _ => config.include_variable_power_toughness,
+            },
+        )
+    }
+}
+
+/// Returns the power and toughness of the card or of each face that has them
+fn power_and_toughness(card: &Card) -> Vec<(String, String)> {
+    if let (Some(power), Some(toughness)) = (&card.power, &card.toughness) {
+        return vec![(power.clone(), toughness.clone())];
+    }
+    card.card_faces
+        .iter()
+        .flatten()
+        .filter_map(|face| match (&face.power, &face.toughness) {
+            (Some(power), Some(toughness)) => Some((power.clone(), toughness.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses a power or toughness, `None` if it isn't a plain number like `*` or `X`
+fn parse_stat(value: &str) -> Option<i64> {
+    value.trim().parse().ok()
+}
+
+fn in_range(value: i64, min: Option<usize>, max: Option<usize>) -> bool {
+    !min.is_some_and(|min| value < min as i64) && !max.is_some_and(|max| value > max as i64)
+}
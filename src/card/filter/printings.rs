@@ -0,0 +1,151 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use log::{debug, warn};
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+use crate::error::Result;
+
+/// Counts how often the oracle card of a [`scryfall::Card`] was printed
+#[async_trait(?Send)]
+pub trait PrintsCounter {
+    async fn count_prints(&self, card: &Card) -> Result<usize>;
+}
+
+pub struct ScryfallPrintsCounter();
+
+#[async_trait(?Send)]
+impl PrintsCounter for ScryfallPrintsCounter {
+    async fn count_prints(&self, card: &Card) -> Result<usize> {
+        let prints = card.prints_search_uri.fetch().await?;
+        Ok(prints.total_cards.unwrap_or(prints.data.len()))
+    }
+}
+
+/// Only lets cards through that were printed at least `DAILY_SCRY_MIN_PRINTINGS` times
+///
+/// The print counts are fetched with the [`PrintsCounter`] once per card and cached afterwards.
+/// If the print count can't be fetched the card is let through.
+pub struct PrintingsFilter<C: PrintsCounter> {
+    counter: C,
+    cache: RefCell<HashMap<String, usize>>,
+}
+
+impl<C: PrintsCounter> PrintingsFilter<C> {
+    pub fn new(counter: C) -> Self {
+        PrintingsFilter {
+            counter,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    async fn prints(&self, card: &Card) -> Result<usize> {
+        let key = card.prints_search_uri.as_ref().to_owned();
+        if let Some(prints) = self.cache.borrow().get(&key) {
+            return Ok(*prints);
+        }
+        let prints = self.counter.count_prints(card).await?;
+        debug!("'{}' was printed {} times", card.name, prints);
+        self.cache.borrow_mut().insert(key, prints);
+        Ok(prints)
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: PrintsCounter> CardFilter for PrintingsFilter<C> {
+    fn name(&self) -> &'static str {
+        "PrintingsFilter"
+    }
+
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!("min printings: {:?}", config.min_printings)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let min_printings = match config.min_printings {
+            Some(min_printings) if min_printings > 1 => min_printings,
+            _ => return true,
+        };
+
+        match self.prints(&card).await {
+            Ok(prints) => prints >= min_printings,
+            Err(error) => {
+                warn!("could not count the prints of '{}': {}", card.name, error);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+    use std::cell::Cell;
+
+    struct TestPrintsCounter {
+        prints: usize,
+        calls: Cell<usize>,
+    }
+
+    #[async_trait(?Send)]
+    impl PrintsCounter for TestPrintsCounter {
+        async fn count_prints(&self, _: &Card) -> Result<usize> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.prints)
+        }
+    }
+
+    fn printings_filter(prints: usize) -> PrintingsFilter<TestPrintsCounter> {
+        PrintingsFilter::new(TestPrintsCounter {
+            prints,
+            calls: Cell::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_min_printings() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let mut config = DailyScryConfig::default();
+        config.min_printings = Some(5);
+
+        assert!(
+            printings_filter(5)
+                .filter(&config, grizzly_bears.clone())
+                .await
+        );
+        assert!(!printings_filter(4).filter(&config, grizzly_bears).await);
+    }
+
+    #[tokio::test]
+    async fn test_min_printings_cached() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let mut config = DailyScryConfig::default();
+        config.min_printings = Some(5);
+
+        let filter = printings_filter(10);
+        assert!(filter.filter(&config, grizzly_bears.clone()).await);
+        assert!(filter.filter(&config, grizzly_bears).await);
+        assert_eq!(filter.counter.calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_min_printings_not_set() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let mut config = DailyScryConfig::default();
+        config.min_printings = None;
+
+        let filter = printings_filter(1);
+        assert!(filter.filter(&config, grizzly_bears).await);
+        assert_eq!(filter.counter.calls.get(), 0);
+    }
+}
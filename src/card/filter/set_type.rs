@@ -0,0 +1,36 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+/// Skips cards whose set type isn't listed in `DAILY_SCRY_ALLOWED_SET_TYPES`, e.g. promos or tokens
+#[derive(Clone)]
+pub struct SetTypeFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for SetTypeFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        let allowed_set_types = config.allowed_set_types.clone().unwrap_or_default();
+        if allowed_set_types.is_empty() {
+            return "allowed set types: all".to_owned();
+        }
+        format!("allowed set types: {:?}", allowed_set_types)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        let allowed_set_types = config.allowed_set_types.clone().unwrap_or_default();
+
+        if allowed_set_types.is_empty() {
+            return true;
+        }
+
+        allowed_set_types.contains(&card.set_type)
+    }
+}
@@ -0,0 +1,52 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use async_trait::async_trait;
+use scryfall::Card;
+
+use crate::card::filter::CardFilter;
+use crate::config::DailyScryConfig;
+
+/// Skips cards whose type line contains one of the types in `DAILY_SCRY_EXCLUDED_TYPES`
+///
+/// Types are compared word by word, so `basic` excludes `Basic Land — Swamp` and
+/// `Basic Snow Land — Forest`.
+#[derive(Clone)]
+pub struct TypeFilter {}
+
+#[async_trait(?Send)]
+impl CardFilter for TypeFilter {
+    fn parameters(&self, config: &DailyScryConfig) -> String {
+        format!("excluded types: {:?}", config.excluded_types)
+    }
+
+    async fn filter(&self, config: &DailyScryConfig, card: Card) -> bool {
+        return !types(&card)
+            .iter()
+            .any(|card_type| config.excluded_types.contains(card_type));
+    }
+}
+
+/// Returns the lowercased words of the type lines of the card and its faces
+fn types(card: &Card) -> Vec<String> {
+    let face_type_lines = card
+        .card_faces
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|face| face.type_line);
+    card.type_line
+        .clone()
+        .into_iter()
+        .chain(face_type_lines)
+        .flat_map(|type_line| {
+            type_line
+                .split_whitespace()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
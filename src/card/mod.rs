@@ -4,65 +4,200 @@
  * SPDX-License-Identifier: MIT
  */
 
-use crate::card::filter::{CardFilter, ContentWarningFilter, IgnoredOracleIdFilter};
+use crate::card::filter::CardFilter;
 use crate::card::random::RandomCardGetter;
 use crate::config::DailyScryConfig;
-use crate::error::Result;
+use crate::error::{Error, Result};
 
-use log::{debug, trace};
+use chrono::{Datelike, NaiveDate};
+use log::{debug, info, trace};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use scryfall::card::Color;
 use scryfall::Card;
+use std::time::Duration;
 
-pub use crate::card::random::DefaultRandomCardGetter;
+pub use crate::card::export::{lookup_cards, parse_ids, ScryfallCardLookup};
+pub use crate::card::filter::build_filters;
+pub use crate::card::printing::{with_finish_printing, with_full_art_printing, with_printed_text};
+pub use crate::card::random::{
+    parse_decklist, DecklistCardGetter, DefaultRandomCardGetter, QueryRandomCardGetter,
+    ScryfallCardResolver,
+};
+pub use crate::card::rulings::{CachedRulings, RulingsFetcher, ScryfallRulingsFetcher};
+pub use crate::card::tokens::tokens;
 
+mod export;
 mod filter;
+mod printing;
 mod random;
+mod rulings;
+mod tokens;
 
+/// Returns a random card that passes all card filters
+///
+/// # Arguments
+///
+/// * `config` - The [`DailyScryConfig`] the filters are configured with
+/// * `random_card_getter` - The [`RandomCardGetter`] the cards are drawn from
+/// * `verbose_card` - Whether to log the decision of every filter for every card
 pub async fn random_card<T: RandomCardGetter>(
     config: &DailyScryConfig,
     mut random_card_getter: T,
+    verbose_card: bool,
 ) -> Result<Card> {
     debug!("calling scryfall to get random card…");
-    let filters_vec: Vec<&dyn CardFilter> =
-        vec![&IgnoredOracleIdFilter {}, &ContentWarningFilter {}];
-    let filters = filters_vec.into_iter();
+    let filters = build_filters(config);
+    let mut rng = StdRng::from_entropy();
 
     let mut card: Card;
+    let mut attempts = 0;
     loop {
+        if config
+            .max_attempts
+            .is_some_and(|max_attempts| attempts >= max_attempts)
+        {
+            return Err(Error::NoCardFound { attempts });
+        }
+        if let Some(delay_ms) = config.request_delay_ms.filter(|_| attempts > 0) {
+            tokio::time::sleep(jittered_delay(delay_ms, &mut rng)).await;
+        }
+        attempts += 1;
+
         card = random_card_getter.get_random_card().await?;
 
-        let filter_results = filters.clone().map(|card_filter| {
-            return (card_filter.filter(config, card.clone()), card_filter.name());
-        });
+        let rejected_by = if verbose_card {
+            let decisions = filter_decisions(&filters, config, &card).await;
+            info!("{}", describe_filter_decisions(&card.name, &decisions));
+            decisions
+                .into_iter()
+                .find(|(_, passed)| !passed)
+                .map(|(name, _)| name)
+        } else {
+            rejecting_filter(&filters, config, &card).await
+        };
 
-        if filter_results.clone().all(|(filtered, _)| filtered) {
-            debug!("all card filters return true");
-            break;
-        }
-
-        let option_name = filter_results.clone().find_map(|(filter, name)| {
-            if filter {
-                return None;
+        match rejected_by {
+            None => {
+                debug!("all card filters return true");
+                break;
             }
-            return Some(name);
-        });
-
-        println!(
-            "'{}' filters '{}' and it will be ignored",
-            option_name.unwrap(),
-            card.clone().name,
-        );
+            Some(name) => info!("'{}' filters '{}' and it will be ignored", name, card.name),
+        }
     }
 
-    trace!("got card with id {}", card.oracle_id.unwrap());
+    trace!("got card with id {:?}", card.oracle_id);
     trace!("{:#?}", card);
     Ok(card)
 }
 
+/// Returns `delay_ms` changed by a random jitter of up to ±50%
+///
+/// This spreads out the requests of instances that were started at the same time.
+fn jittered_delay<R: Rng>(delay_ms: usize, rng: &mut R) -> Duration {
+    let factor = rng.gen_range(0.5..=1.5);
+    Duration::from_millis((delay_ms as f64 * factor) as u64)
+}
+
+/// Returns the name of the first filter that rejects `card`, if any
+async fn rejecting_filter(
+    filters: &[Box<dyn CardFilter>],
+    config: &DailyScryConfig,
+    card: &Card,
+) -> Option<&'static str> {
+    for card_filter in filters {
+        if !card_filter.filter(config, card.clone()).await {
+            return Some(card_filter.name());
+        }
+    }
+    None
+}
+
+/// Returns the name and the decision of every filter for `card`
+async fn filter_decisions(
+    filters: &[Box<dyn CardFilter>],
+    config: &DailyScryConfig,
+    card: &Card,
+) -> Vec<(&'static str, bool)> {
+    let mut decisions = vec![];
+    for card_filter in filters {
+        decisions.push((
+            card_filter.name(),
+            card_filter.filter(config, card.clone()).await,
+        ));
+    }
+    decisions
+}
+
+fn describe_filter_decisions(card_name: &str, decisions: &[(&'static str, bool)]) -> String {
+    format!(
+        "filter decisions for '{}': {}",
+        card_name,
+        decisions
+            .iter()
+            .map(|(name, passed)| format!("{}={}", name, passed))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Returns the scryfall query configured for the weekday of `date`, if any
+///
+/// # Arguments
+///
+/// * `config` - The [`DailyScryConfig`] holding the weekday to query mapping
+/// * `date` - The date whose weekday is looked up
+pub fn query_for_weekday(config: &DailyScryConfig, date: NaiveDate) -> Option<String> {
+    config
+        .weekday_queries
+        .as_ref()
+        .and_then(|weekday_queries| weekday_queries.get(&date.weekday()).cloned())
+}
+
+/// Returns a scryfall query for commander legal cards within the color identity of a commander
+///
+/// # Arguments
+///
+/// * `color_identity` - The color identity of the commander
+pub fn query_for_commander(color_identity: &[Color]) -> String {
+    let colors = color_identity
+        .iter()
+        .filter_map(|color| serde_json::to_value(color).ok())
+        .filter_map(|color| color.as_str().map(|color| color.to_lowercase()))
+        .collect::<String>();
+    if colors.is_empty() {
+        return "id:c legal:commander".to_owned();
+    }
+    format!("id<={} legal:commander", colors)
+}
+
+/// The year the first magic cards were released in
+const FIRST_RELEASE_YEAR: i32 = 1993;
+
+/// Returns a scryfall query for cards first released on the month and day of `date` in any year
+///
+/// # Arguments
+///
+/// * `date` - The date whose month and day are looked up
+pub fn query_for_day(date: NaiveDate) -> String {
+    let dates = (FIRST_RELEASE_YEAR..=date.year())
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, date.month(), date.day()))
+        .map(|day| format!("date={}", day.format("%Y-%m-%d")))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    format!("not:reprint ({})", dates)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::error::Error;
+    use crate::config::DailyScryConfigBuilder;
 
     use super::*;
+    use crate::fixtures::card_fixture;
+    use chrono::Weekday;
+    use scryfall::card::{BorderColor, Layout};
+    use scryfall::set::SetType;
+    use std::collections::HashMap;
 
     struct TestCardGetter {
         call_index: usize,
@@ -94,17 +229,13 @@ mod tests {
     fn build_config(ignored_oracle_id: Option<&str>) -> DailyScryConfig {
         let ignored_oracle_ids =
             ignored_oracle_id.map(|oracle_id| vec![oracle_id.parse().unwrap()]);
-        DailyScryConfig {
-            mastodon_url: None,
-            mastodon_access_token: None,
-            mastodon_character_limit: None,
-            telegram_token: None,
-            telegram_chat_id: None,
-            telegram_character_limit: None,
-            image_path: "test/".to_string(),
-            ignored_oracle_ids: ignored_oracle_ids,
-            version: "Test_Version".to_string(),
-        }
+        DailyScryConfigBuilder::new()
+            .with_mastodon_character_limit(None)
+            .with_telegram_character_limit(None)
+            .with_image_path("test/".to_owned())
+            .with_ignored_oracle_ids(ignored_oracle_ids)
+            .with_version("Test_Version".to_owned())
+            .build()
     }
 
     #[tokio::test]
@@ -120,10 +251,51 @@ mod tests {
                 .unwrap(),
         ]);
 
-        let card = random_card(&config, card_getter).await.unwrap();
+        let card = random_card(&config, card_getter, false).await.unwrap();
         assert_eq!(card.name, "Black Lotus")
     }
 
+    #[tokio::test]
+    async fn test_max_attempts() {
+        let mut config = build_config(Some("56719f6a-1a6c-4c0a-8d21-18f7d7350b68"));
+        config.max_attempts = Some(2);
+        config.request_delay_ms = Some(10);
+
+        let swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+        let card_getter =
+            TestCardGetter::new(vec![swamp.clone(), swamp, card_fixture("grizzly_bears")]);
+
+        let result = random_card(&config, card_getter, false).await;
+        assert!(matches!(result, Err(Error::NoCardFound { attempts: 2 })));
+    }
+
+    #[tokio::test]
+    async fn test_max_attempts_strict_filters() {
+        let mut config = build_config(None);
+        config.allowed_layouts = Some(vec![Layout::Split]);
+        config.max_attempts = Some(3);
+
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let card_getter = TestCardGetter::new(vec![grizzly_bears; 4]);
+
+        let result = random_card(&config, card_getter, false).await;
+        assert!(matches!(result, Err(Error::NoCardFound { attempts: 3 })));
+    }
+
+    #[test]
+    fn test_jittered_delay() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let delay = jittered_delay(1000, &mut rng);
+            assert!(delay >= Duration::from_millis(500));
+            assert!(delay <= Duration::from_millis(1500));
+        }
+        assert_eq!(jittered_delay(0, &mut rng), Duration::ZERO);
+    }
+
     #[tokio::test]
     async fn test_ignored_oracle_id_no_ids_ignored() {
         let config = build_config(None);
@@ -134,7 +306,43 @@ mod tests {
         .await
         .unwrap()]);
 
-        let card = random_card(&config, card_getter).await.unwrap();
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Swamp")
+    }
+
+    #[tokio::test]
+    async fn test_no_oracle_id_allowed() {
+        let config = build_config(None);
+
+        let mut black_lotus =
+            Card::scryfall_id("b0faa7f2-b547-42c4-a810-839da50dadfe".parse().unwrap())
+                .await
+                .unwrap();
+        black_lotus.oracle_id = None;
+        let card_getter = TestCardGetter::new(vec![black_lotus]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Black Lotus")
+    }
+
+    #[tokio::test]
+    async fn test_no_oracle_id_rejected() {
+        let mut config = build_config(None);
+        config.allow_no_oracle_id = false;
+
+        let mut black_lotus =
+            Card::scryfall_id("b0faa7f2-b547-42c4-a810-839da50dadfe".parse().unwrap())
+                .await
+                .unwrap();
+        black_lotus.oracle_id = None;
+        let card_getter = TestCardGetter::new(vec![
+            black_lotus,
+            Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+                .await
+                .unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
         assert_eq!(card.name, "Swamp")
     }
 
@@ -151,7 +359,383 @@ mod tests {
                 .unwrap(),
         ]);
 
-        let card = random_card(&config, card_getter).await.unwrap();
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Black Lotus")
+    }
+
+    #[tokio::test]
+    async fn test_allowed_layouts() {
+        let mut config = build_config(None);
+        config.allowed_layouts = Some(vec![Layout::Transform]);
+
+        let card_getter = TestCardGetter::new(vec![
+            Card::scryfall_id("b0faa7f2-b547-42c4-a810-839da50dadfe".parse().unwrap())
+                .await
+                .unwrap(),
+            Card::multiverse(398428).await.unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.layout, Layout::Transform);
+        assert_eq!(card.name, "Kytheon, Hero of Akros // Gideon, Battle-Forged")
+    }
+
+    #[tokio::test]
+    async fn test_excluded_types() {
+        let mut config = build_config(None);
+        config.excluded_types = vec!["basic".to_owned()];
+
+        let card_getter = TestCardGetter::new(vec![
+            Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+                .await
+                .unwrap(),
+            Card::multiverse(197).await.unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Fireball")
+    }
+
+    #[tokio::test]
+    async fn test_require_flavor_text() {
+        let mut config = build_config(None);
+        config.require_flavor_text = true;
+
+        let card_getter = TestCardGetter::new(vec![
+            Card::scryfall_id("b0faa7f2-b547-42c4-a810-839da50dadfe".parse().unwrap())
+                .await
+                .unwrap(),
+            Card::multiverse(2497).await.unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Brainstorm")
+    }
+
+    #[tokio::test]
+    async fn test_require_flavor_text_disabled() {
+        let config = build_config(None);
+
+        let card_getter = TestCardGetter::new(vec![Card::scryfall_id(
+            "b0faa7f2-b547-42c4-a810-839da50dadfe".parse().unwrap(),
+        )
+        .await
+        .unwrap()]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
         assert_eq!(card.name, "Black Lotus")
     }
+
+    #[tokio::test]
+    async fn test_exclude_funny() {
+        let mut config = build_config(None);
+        config.exclude_funny = true;
+
+        let adorable_kitten = Card::named("Adorable Kitten").await.unwrap();
+        let mut silver_bordered = card_fixture("grizzly_bears");
+        silver_bordered.name = "Silver Bordered".to_owned();
+        silver_bordered.border_color = BorderColor::Silver;
+        let grizzly_bears = card_fixture("grizzly_bears");
+
+        let card_getter = TestCardGetter::new(vec![
+            adorable_kitten.clone(),
+            silver_bordered,
+            grizzly_bears,
+        ]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Grizzly Bears");
+
+        config.exclude_funny = false;
+        let card_getter = TestCardGetter::new(vec![adorable_kitten]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Adorable Kitten")
+    }
+
+    #[tokio::test]
+    async fn test_require_highres() {
+        let mut config = build_config(None);
+        config.require_highres = true;
+
+        let mut lowres = card_fixture("grizzly_bears");
+        lowres.name = "Lowres".to_owned();
+        lowres.highres_image = false;
+        let mut highres = card_fixture("grizzly_bears");
+        highres.name = "Highres".to_owned();
+        highres.highres_image = true;
+
+        let card_getter = TestCardGetter::new(vec![lowres.clone(), highres]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Highres");
+
+        config.require_highres = false;
+        let card_getter = TestCardGetter::new(vec![lowres]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Lowres")
+    }
+
+    #[tokio::test]
+    async fn test_allowed_set_types() {
+        let mut config = build_config(None);
+        config.allowed_set_types = Some(vec![SetType::Core, SetType::Expansion]);
+
+        let mut promo = card_fixture("grizzly_bears");
+        promo.name = "Promo".to_owned();
+        promo.set_type = SetType::Promo;
+        let mut expansion = card_fixture("grizzly_bears");
+        expansion.name = "Expansion".to_owned();
+        expansion.set_type = SetType::Expansion;
+
+        let card_getter = TestCardGetter::new(vec![promo.clone(), expansion]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Expansion");
+
+        config.allowed_set_types = Some(vec![]);
+        let card_getter = TestCardGetter::new(vec![promo]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Promo")
+    }
+
+    #[tokio::test]
+    async fn test_min_oracle_len() {
+        let mut config = build_config(None);
+        config.min_oracle_len = Some(200);
+
+        let card_getter = TestCardGetter::new(vec![
+            card_fixture("grizzly_bears"),
+            Card::named("Karn Liberated").await.unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Karn Liberated")
+    }
+
+    #[tokio::test]
+    async fn test_max_oracle_len() {
+        let mut config = build_config(None);
+        config.max_oracle_len = Some(100);
+
+        let card_getter = TestCardGetter::new(vec![
+            Card::named("Karn Liberated").await.unwrap(),
+            card_fixture("grizzly_bears"),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Grizzly Bears")
+    }
+
+    fn creature_with(power: &str, toughness: &str) -> Card {
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.name = format!("{}/{}", power, toughness);
+        grizzly_bears.power = Some(power.to_owned());
+        grizzly_bears.toughness = Some(toughness.to_owned());
+        grizzly_bears
+    }
+
+    #[tokio::test]
+    async fn test_min_power() {
+        let mut config = build_config(None);
+        config.min_power = Some(5);
+
+        let card_getter = TestCardGetter::new(vec![
+            creature_with("1", "1"),
+            creature_with("*", "*"),
+            creature_with("10", "10"),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "10/10")
+    }
+
+    #[tokio::test]
+    async fn test_max_toughness() {
+        let mut config = build_config(None);
+        config.max_toughness = Some(5);
+
+        let card_getter =
+            TestCardGetter::new(vec![creature_with("10", "10"), creature_with("1", "1")]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "1/1")
+    }
+
+    #[tokio::test]
+    async fn test_power_toughness_variable_and_non_creatures() {
+        let mut config = build_config(None);
+        config.min_power = Some(5);
+        config.include_variable_power_toughness = true;
+
+        let card_getter =
+            TestCardGetter::new(vec![creature_with("1", "1"), creature_with("*", "*")]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "*/*");
+
+        let card_getter = TestCardGetter::new(vec![Card::multiverse(197).await.unwrap()]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Fireball");
+
+        config.power_toughness_creatures_only = true;
+        let card_getter = TestCardGetter::new(vec![
+            Card::multiverse(197).await.unwrap(),
+            creature_with("10", "10"),
+        ]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "10/10")
+    }
+
+    #[tokio::test]
+    async fn test_allowed_artists() {
+        let mut config = build_config(None);
+        config.allowed_artists = vec!["jeff a. menges".to_owned()];
+
+        let card_getter = TestCardGetter::new(vec![
+            Card::multiverse(197).await.unwrap(),
+            card_fixture("grizzly_bears"),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Grizzly Bears")
+    }
+
+    #[tokio::test]
+    async fn test_excluded_artists() {
+        let mut config = build_config(None);
+        config.excluded_artists = vec!["jeff a. menges".to_owned()];
+
+        let card_getter = TestCardGetter::new(vec![
+            card_fixture("grizzly_bears"),
+            Card::multiverse(197).await.unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Fireball")
+    }
+
+    #[tokio::test]
+    async fn test_excluded_keywords() {
+        let mut config = build_config(None);
+        config.excluded_keywords = vec!["flying".to_owned()];
+
+        let card_getter = TestCardGetter::new(vec![
+            Card::named("Serra Angel").await.unwrap(),
+            Card::multiverse(197).await.unwrap(),
+        ]);
+
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Fireball")
+    }
+
+    #[tokio::test]
+    async fn test_excluded_keywords_in_oracle_text() {
+        let mut config = build_config(None);
+        config.excluded_keywords = vec!["divided evenly".to_owned()];
+
+        let card_getter = TestCardGetter::new(vec![Card::multiverse(197).await.unwrap()]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Fireball");
+
+        config.exclude_keywords_in_oracle_text = true;
+        let card_getter = TestCardGetter::new(vec![
+            Card::multiverse(197).await.unwrap(),
+            card_fixture("grizzly_bears"),
+        ]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Grizzly Bears")
+    }
+
+    #[tokio::test]
+    async fn test_allowed_artists_multiple_artists() {
+        let mut config = build_config(None);
+        config.allowed_artists = vec!["nils hamm".to_owned()];
+
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.artist = Some("Jeff A. Menges & Nils Hamm".to_owned());
+        let card_getter = TestCardGetter::new(vec![grizzly_bears.clone()]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Grizzly Bears");
+
+        config.allowed_artists = vec![];
+        config.excluded_artists = vec!["nils hamm".to_owned()];
+        let card_getter =
+            TestCardGetter::new(vec![grizzly_bears, Card::multiverse(197).await.unwrap()]);
+        let card = random_card(&config, card_getter, false).await.unwrap();
+        assert_eq!(card.name, "Fireball")
+    }
+
+    #[tokio::test]
+    async fn test_describe_filter_decisions() {
+        let config = build_config(Some("56719f6a-1a6c-4c0a-8d21-18f7d7350b68"));
+        let swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+
+        let filters = build_filters(&config);
+        let decisions = filter_decisions(&filters, &config, &swamp).await;
+        let description = describe_filter_decisions(&swamp.name, &decisions);
+
+        assert!(description.starts_with("filter decisions for 'Swamp': "));
+        assert!(description.contains("IgnoredOracleIdFilter=false"));
+        for card_filter in &filters {
+            assert!(description.contains(card_filter.name()));
+        }
+    }
+
+    #[test]
+    fn test_filter_parameters() {
+        let mut config = build_config(None);
+        config.allowed_layouts = Some(vec![Layout::Normal]);
+        config.min_printings = Some(5);
+
+        let parameters = build_filters(&config)
+            .iter()
+            .map(|card_filter| (card_filter.name(), card_filter.parameters(&config)))
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(parameters["LayoutFilter"], "allowed layouts: [Normal]");
+        assert_eq!(parameters["PrintingsFilter"], "min printings: Some(5)");
+        assert_eq!(parameters["ContentWarningFilter"], "");
+    }
+
+    #[test]
+    fn test_query_for_weekday() {
+        let mut config = build_config(None);
+        config.weekday_queries = Some(HashMap::from([(Weekday::Tue, "t:goblin".to_owned())]));
+
+        let tuesday = NaiveDate::from_ymd_opt(2024, 4, 2).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+
+        assert_eq!(
+            query_for_weekday(&config, tuesday),
+            Some("t:goblin".to_owned())
+        );
+        assert_eq!(query_for_weekday(&config, monday), None);
+    }
+
+    #[test]
+    fn test_query_for_commander() {
+        assert_eq!(
+            query_for_commander(&[Color::White, Color::Blue]),
+            "id<=wu legal:commander"
+        );
+        assert_eq!(query_for_commander(&[]), "id:c legal:commander");
+    }
+
+    #[test]
+    fn test_query_for_day() {
+        let date = NaiveDate::from_ymd_opt(1995, 8, 5).unwrap();
+
+        assert_eq!(
+            query_for_day(date),
+            "not:reprint (date=1993-08-05 or date=1994-08-05 or date=1995-08-05)"
+        );
+    }
+
+    #[test]
+    fn test_query_for_day_leap_day() {
+        let date = NaiveDate::from_ymd_opt(2000, 2, 29).unwrap();
+
+        assert_eq!(
+            query_for_day(date),
+            "not:reprint (date=1996-02-29 or date=2000-02-29)"
+        );
+    }
 }
@@ -0,0 +1,166 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use crate::error::Result;
+use crate::image::select_finish_printing;
+
+use log::debug;
+use scryfall::card::{Finishes, FrameEffect};
+use scryfall::Card;
+
+/// Returns `card` with the image and artist of a full-art or showcase printing, if there is one
+///
+/// The oracle text, name and everything else are kept from `card`.
+///
+/// # Arguments
+///
+/// * `card` - The [`scryfall::card::Card`] whose printings are searched
+pub async fn with_full_art_printing(card: &Card) -> Result<Card> {
+    let prints = card.prints_search_uri.fetch().await?.data;
+    return Ok(match select_full_art_printing(&prints) {
+        Some(printing) => {
+            debug!("using art of printing {} ({})", printing.id, printing.set);
+            with_printing_art(card, printing)
+        }
+        None => card.clone(),
+    });
+}
+
+/// Returns `card` with the image and artist of a printing in the given finish, if it looks
+/// different
+///
+/// # Arguments
+///
+/// * `card` - The [`scryfall::card::Card`] whose printings are searched
+/// * `finish` - The preferred finish
+pub async fn with_finish_printing(card: &Card, finish: Finishes) -> Result<Card> {
+    if card.finishes.contains(&finish) {
+        return Ok(card.clone());
+    }
+    let prints = card.prints_search_uri.fetch().await?.data;
+    return Ok(match select_finish_printing(card, &prints, finish) {
+        Some(printing) => {
+            debug!(
+                "using art of {:?} printing {} ({})",
+                finish, printing.id, printing.set
+            );
+            with_printing_art(card, printing)
+        }
+        None => card.clone(),
+    });
+}
+
+/// Returns `card` with the name and text as printed on it, e.g. in another language
+///
+/// Every field the printing has no printed value for keeps its English oracle value. The type
+/// line stays English, since the layout and sieges are detected from it. The printed type line
+/// is shown instead by the formatted text, see `FormatOptions::printed_type_line`.
+///
+/// # Arguments
+///
+/// * `card` - The [`scryfall::card::Card`] whose printed text is used
+pub fn with_printed_text(card: &Card) -> Card {
+    let mut card = card.clone();
+
+    card.name = card.printed_name.clone().unwrap_or(card.name);
+    card.oracle_text = card.printed_text.clone().or(card.oracle_text);
+
+    for face in card.card_faces.iter_mut().flatten() {
+        face.name = face.printed_name.clone().unwrap_or(face.name.clone());
+        face.oracle_text = face.printed_text.clone().or(face.oracle_text.take());
+    }
+
+    return card;
+}
+
+fn select_full_art_printing(prints: &[Card]) -> Option<&Card> {
+    let is_showcase = |printing: &&Card| {
+        printing
+            .frame_effects
+            .clone()
+            .unwrap_or_default()
+            .contains(&FrameEffect::Showcase)
+    };
+    return prints
+        .iter()
+        .find(|printing| printing.full_art)
+        .or_else(|| prints.iter().find(is_showcase));
+}
+
+fn with_printing_art(card: &Card, printing: &Card) -> Card {
+    let mut card = card.clone();
+
+    if card.layout != printing.layout {
+        return card;
+    }
+
+    card.image_uris = printing.image_uris.clone();
+    card.artist = printing.artist.clone();
+    card.illustration_id = printing.illustration_id;
+
+    if let (Some(faces), Some(printing_faces)) = (&mut card.card_faces, &printing.card_faces) {
+        for (face, printing_face) in faces.iter_mut().zip(printing_faces.iter()) {
+            face.image_uris = printing_face.image_uris.clone();
+            face.artist = printing_face.artist.clone();
+        }
+    }
+
+    return card;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+
+    #[test]
+    fn test_with_printed_text() {
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.lang = "de".to_owned();
+        grizzly_bears.printed_name = Some("Grizzlybären".to_owned());
+        grizzly_bears.printed_type_line = Some("Kreatur — Bär".to_owned());
+        grizzly_bears.printed_text = None;
+
+        let card = with_printed_text(&grizzly_bears);
+        assert_eq!(card.name, "Grizzlybären");
+        assert_eq!(card.type_line.as_deref(), Some("Creature — Bear"));
+        assert_eq!(card.printed_type_line.as_deref(), Some("Kreatur — Bär"));
+        assert_eq!(card.oracle_text, grizzly_bears.oracle_text);
+
+        let english = with_printed_text(&card_fixture("grizzly_bears"));
+        assert_eq!(english.name, "Grizzly Bears");
+        assert_eq!(english.type_line.as_deref(), Some("Creature — Bear"));
+    }
+
+    #[tokio::test]
+    async fn test_select_full_art_printing() {
+        let swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+        let mut full_art_swamp = swamp.clone();
+        full_art_swamp.full_art = true;
+        full_art_swamp.artist = Some("Full Art Artist".to_owned());
+        let mut showcase_swamp = swamp.clone();
+        showcase_swamp.frame_effects = Some(vec![FrameEffect::Showcase]);
+
+        let prints = vec![
+            swamp.clone(),
+            showcase_swamp.clone(),
+            full_art_swamp.clone(),
+        ];
+        assert_eq!(select_full_art_printing(&prints), Some(&full_art_swamp));
+
+        let prints = vec![swamp.clone(), showcase_swamp.clone()];
+        assert_eq!(select_full_art_printing(&prints), Some(&showcase_swamp));
+
+        let prints = vec![swamp.clone()];
+        assert_eq!(select_full_art_printing(&prints), None);
+
+        let card = with_printing_art(&swamp, &full_art_swamp);
+        assert_eq!(card.artist.as_deref(), Some("Full Art Artist"));
+        assert_eq!(card.full_art, false);
+    }
+}
@@ -6,7 +6,10 @@
 
 use crate::error::Result;
 
+use log::debug;
+use rand::Rng;
 use scryfall::Card;
+use std::collections::HashMap;
 
 pub trait RandomCardGetter {
     async fn get_random_card(&mut self) -> Result<Card>;
@@ -19,3 +22,205 @@ impl RandomCardGetter for DefaultRandomCardGetter {
         Ok(Card::random().await?)
     }
 }
+
+pub struct QueryRandomCardGetter {
+    query: String,
+}
+
+impl QueryRandomCardGetter {
+    pub fn new(query: String) -> Self {
+        QueryRandomCardGetter { query }
+    }
+}
+
+impl RandomCardGetter for QueryRandomCardGetter {
+    async fn get_random_card(&mut self) -> Result<Card> {
+        Ok(Card::search_random(self.query.as_str()).await?)
+    }
+}
+
+/// A single line of a decklist like `4 Lightning Bolt (M10)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecklistEntry {
+    pub quantity: usize,
+    pub name: String,
+    pub set: Option<String>,
+}
+
+/// Parses a plain text decklist
+///
+/// Every line holds an optional quantity (`4` or `4x`), the card name and an optional set code
+/// in round or square brackets, e.g. `4 Lightning Bolt [M10]`. Anything after the set code like
+/// a collector number is ignored. Empty lines and lines starting with `//` or `#` are skipped.
+///
+/// # Arguments
+///
+/// * `decklist` - The content of the decklist
+pub fn parse_decklist(decklist: &str) -> Vec<DecklistEntry> {
+    decklist
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("//") && !line.starts_with('#'))
+        .filter_map(parse_decklist_line)
+        .collect()
+}
+
+fn parse_decklist_line(line: &str) -> Option<DecklistEntry> {
+    let (quantity, rest) = match line.split_once(' ') {
+        Some((first, rest)) => match first.trim_end_matches('x').parse::<usize>() {
+            Ok(quantity) => (quantity, rest.trim()),
+            Err(_) => (1, line),
+        },
+        None => (1, line),
+    };
+
+    let (name, set) = match rest.find(|character| character == '(' || character == '[') {
+        Some(start) => {
+            let set = rest[start + 1..]
+                .split(|character| character == ')' || character == ']')
+                .next()
+                .map(|set| set.trim().to_lowercase())
+                .filter(|set| !set.is_empty());
+            (rest[..start].trim(), set)
+        }
+        None => (rest, None),
+    };
+
+    if quantity == 0 || name.is_empty() {
+        return None;
+    }
+
+    Some(DecklistEntry {
+        quantity,
+        name: name.to_owned(),
+        set,
+    })
+}
+
+pub trait CardResolver {
+    async fn resolve(&self, entry: &DecklistEntry) -> Result<Card>;
+}
+
+pub struct ScryfallCardResolver();
+
+impl CardResolver for ScryfallCardResolver {
+    async fn resolve(&self, entry: &DecklistEntry) -> Result<Card> {
+        Ok(match &entry.set {
+            Some(set) => Card::search_random(&format!("!\"{}\" set:{}", entry.name, set)).await?,
+            None => Card::named(&entry.name).await?,
+        })
+    }
+}
+
+/// Picks a random card from a decklist, weighted by the quantities
+///
+/// Cards are resolved with the [`CardResolver`] once and cached afterwards.
+pub struct DecklistCardGetter<R: CardResolver> {
+    entries: Vec<DecklistEntry>,
+    resolver: R,
+    cache: HashMap<usize, Card>,
+}
+
+impl<R: CardResolver> DecklistCardGetter<R> {
+    pub fn new(entries: Vec<DecklistEntry>, resolver: R) -> Self {
+        DecklistCardGetter {
+            entries,
+            resolver,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn pick_index(&self) -> usize {
+        let total: usize = self.entries.iter().map(|entry| entry.quantity).sum();
+        let mut pick = rand::thread_rng().gen_range(0..total);
+        for (index, entry) in self.entries.iter().enumerate() {
+            if pick < entry.quantity {
+                return index;
+            }
+            pick -= entry.quantity;
+        }
+        self.entries.len() - 1
+    }
+}
+
+impl<R: CardResolver> RandomCardGetter for DecklistCardGetter<R> {
+    async fn get_random_card(&mut self) -> Result<Card> {
+        let index = self.pick_index();
+        if let Some(card) = self.cache.get(&index) {
+            return Ok(card.clone());
+        }
+
+        debug!("resolving decklist entry {:?}", self.entries[index]);
+        let card = self.resolver.resolve(&self.entries[index]).await?;
+        self.cache.insert(index, card.clone());
+        Ok(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_parse_decklist() {
+        let decklist = "// Burn\n\
+        4 Lightning Bolt [M10]\n\
+        2x Goblin Guide (ZEN) 126\n\
+        \n\
+        # Lands\n\
+        Mountain\n\
+        0 Island";
+        assert_eq!(
+            parse_decklist(decklist),
+            vec![
+                DecklistEntry {
+                    quantity: 4,
+                    name: "Lightning Bolt".to_owned(),
+                    set: Some("m10".to_owned()),
+                },
+                DecklistEntry {
+                    quantity: 2,
+                    name: "Goblin Guide".to_owned(),
+                    set: Some("zen".to_owned()),
+                },
+                DecklistEntry {
+                    quantity: 1,
+                    name: "Mountain".to_owned(),
+                    set: None,
+                },
+            ]
+        );
+    }
+
+    struct TestCardResolver {
+        card: Card,
+        calls: Cell<usize>,
+    }
+
+    impl CardResolver for TestCardResolver {
+        async fn resolve(&self, entry: &DecklistEntry) -> Result<Card> {
+            self.calls.set(self.calls.get() + 1);
+            let mut card = self.card.clone();
+            card.name = entry.name.clone();
+            Ok(card)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decklist_card_getter() {
+        let resolver = TestCardResolver {
+            card: card_fixture("grizzly_bears"),
+            calls: Cell::new(0),
+        };
+        let mut card_getter =
+            DecklistCardGetter::new(parse_decklist("3 Lightning Bolt [M10]"), resolver);
+
+        let card = card_getter.get_random_card().await.unwrap();
+        assert_eq!(card.name, "Lightning Bolt");
+        let card = card_getter.get_random_card().await.unwrap();
+        assert_eq!(card.name, "Lightning Bolt");
+        assert_eq!(card_getter.resolver.calls.get(), 1);
+    }
+}
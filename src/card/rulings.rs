@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use crate::error::Result;
+
+use async_trait::async_trait;
+use log::debug;
+use scryfall::ruling::Ruling;
+use scryfall::Card;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[async_trait(?Send)]
+pub trait RulingsFetcher {
+    async fn fetch_rulings(&self, card: &Card) -> Result<Vec<Ruling>>;
+}
+
+pub struct ScryfallRulingsFetcher();
+
+#[async_trait(?Send)]
+impl RulingsFetcher for ScryfallRulingsFetcher {
+    async fn fetch_rulings(&self, card: &Card) -> Result<Vec<Ruling>> {
+        Ok(card.rulings_uri.fetch().await?.data)
+    }
+}
+
+/// Fetches the rulings of every card once, even if it is previewed before it is posted
+pub struct CachedRulings<F: RulingsFetcher> {
+    fetcher: F,
+    cache: RefCell<HashMap<Uuid, Vec<Ruling>>>,
+}
+
+impl<F: RulingsFetcher> CachedRulings<F> {
+    pub fn new(fetcher: F) -> Self {
+        CachedRulings {
+            fetcher,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the rulings of `card`, printings of the same card share them
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - The [`scryfall::card::Card`] whose rulings are fetched
+    pub async fn rulings(&self, card: &Card) -> Result<Vec<Ruling>> {
+        let key = card.oracle_id.unwrap_or(card.id);
+        if let Some(rulings) = self.cache.borrow().get(&key) {
+            return Ok(rulings.clone());
+        }
+        debug!("fetching rulings of {}", card.name);
+        let rulings = self.fetcher.fetch_rulings(card).await?;
+        self.cache.borrow_mut().insert(key, rulings.clone());
+        Ok(rulings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::cell::Cell;
+
+    struct TestRulingsFetcher {
+        fetches: Cell<usize>,
+    }
+
+    #[async_trait(?Send)]
+    impl RulingsFetcher for TestRulingsFetcher {
+        async fn fetch_rulings(&self, card: &Card) -> Result<Vec<Ruling>> {
+            self.fetches.set(self.fetches.get() + 1);
+            Ok(vec![serde_json::from_value(json!({
+                "object": "ruling",
+                "oracle_id": card.oracle_id,
+                "source": "wotc",
+                "published_at": "2004-10-04",
+                "comment": "The damage is divided evenly, rounded down.",
+            }))
+            .unwrap()])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_rulings() {
+        let fireball = Card::multiverse(197).await.unwrap();
+        let rulings = CachedRulings::new(TestRulingsFetcher {
+            fetches: Cell::new(0),
+        });
+
+        assert_eq!(rulings.rulings(&fireball).await.unwrap().len(), 1);
+        assert_eq!(rulings.rulings(&fireball).await.unwrap().len(), 1);
+        assert_eq!(rulings.fetcher.fetches.get(), 1);
+    }
+}
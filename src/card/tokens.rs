@@ -0,0 +1,59 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use crate::error::Result;
+
+use log::debug;
+use scryfall::card::{Component, RelatedCard};
+use scryfall::Card;
+
+/// Returns the tokens `card` creates
+///
+/// The tokens are found in the `all_parts` of the card.
+///
+/// # Arguments
+///
+/// * `card` - The [`scryfall::card::Card`] whose tokens are fetched
+pub async fn tokens(card: &Card) -> Result<Vec<Card>> {
+    let mut tokens = vec![];
+    for part in token_parts(card) {
+        debug!("fetching token {} of {}", part.name, card.name);
+        tokens.push(part.uri.fetch().await?);
+    }
+    Ok(tokens)
+}
+
+fn token_parts(card: &Card) -> Vec<RelatedCard> {
+    card.all_parts
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|part| part.component == Component::Token && part.id != card.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+
+    #[tokio::test]
+    async fn test_token_parts_alive_and_well() {
+        let alive_and_well = Card::multiverse(369041).await.unwrap();
+
+        let parts = token_parts(&alive_and_well);
+
+        assert!(!parts.is_empty());
+        assert!(parts.iter().all(|part| part.component == Component::Token));
+        assert!(parts.iter().any(|part| part.name == "Centaur"));
+    }
+
+    #[test]
+    fn test_token_parts_without_tokens() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        assert!(token_parts(&grizzly_bears).is_empty());
+    }
+}
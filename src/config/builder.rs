@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use crate::config::DailyScryConfig;
+
+use uuid::Uuid;
+
+/// Builds a [`DailyScryConfig`] without reading the environment
+///
+/// Every field starts with the value [`DailyScryConfig::default()`] uses. The fields tests
+/// need to change have a matching `with_*` setter, e.g.
+///
+/// ```ignore
+/// let config = DailyScryConfigBuilder::new()
+///     .with_min_printings(5)
+///     .with_require_flavor_text(true)
+///     .build();
+/// ```
+///
+/// DailyScry is only built as a binary, so nothing outside of the crate can embed the
+/// configuration and the builder is only compiled for tests.
+#[derive(Debug, Default)]
+pub struct DailyScryConfigBuilder {
+    config: DailyScryConfig,
+}
+
+macro_rules! setters {
+    ($($setter:ident => $field:ident: $type:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("Sets `", stringify!($field), "`")]
+            pub fn $setter(mut self, $field: impl Into<$type>) -> Self {
+                self.config.$field = $field.into();
+                self
+            }
+        )*
+    };
+}
+
+impl DailyScryConfigBuilder {
+    pub fn new() -> Self {
+        DailyScryConfigBuilder::default()
+    }
+
+    pub fn build(self) -> DailyScryConfig {
+        self.config
+    }
+
+    setters! {
+        with_mastodon_url => mastodon_url: Option<String>,
+        with_mastodon_character_limit => mastodon_character_limit: Option<usize>,
+        with_telegram_character_limit => telegram_character_limit: Option<usize>,
+        with_image_path => image_path: String,
+        with_ignored_oracle_ids => ignored_oracle_ids: Option<Vec<Uuid>>,
+        with_require_flavor_text => require_flavor_text: bool,
+        with_min_printings => min_printings: Option<usize>,
+        with_version => version: String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults() {
+        let config = DailyScryConfigBuilder::new().build();
+
+        assert_eq!(config.mastodon_character_limit, Some(500));
+        assert_eq!(config.telegram_character_limit, Some(4096));
+        assert_eq!(config.ignored_oracle_ids, Some(vec![]));
+        assert_eq!(config.artist_template, "Illustrated by {artist}");
+        assert!(config.allow_no_oracle_id);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder_setters() {
+        let config = DailyScryConfigBuilder::new()
+            .with_min_printings(5)
+            .with_mastodon_url("https://botsin.space".to_owned())
+            .with_require_flavor_text(true)
+            .build();
+
+        assert_eq!(config.min_printings, Some(5));
+        assert_eq!(config.mastodon_url, Some("https://botsin.space".to_owned()));
+        assert!(config.require_flavor_text);
+    }
+}
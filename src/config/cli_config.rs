@@ -6,6 +6,7 @@
 
 use clap::Parser;
 use clap_verbosity_flag::Verbosity;
+use std::path::PathBuf;
 
 const HELP_TEMPLATE: &str = "\
 {before-help}{about-with-newline}
@@ -33,6 +34,70 @@ pub struct CLIConfig {
 
     #[arg(long, help = "Run the command without posting anything")]
     pub dry_run: bool,
+
+    #[arg(long, help = "Show a preview and ask before posting")]
+    pub interactive: bool,
+
+    #[arg(
+        long,
+        help = "Post a labeled test card to the enabled backends to verify delivery"
+    )]
+    pub self_test: bool,
+
+    #[arg(
+        long,
+        help = "Only select a card, print its name and oracle id and exit"
+    )]
+    pub fetch_only: bool,
+
+    #[arg(long, help = "Log the decision of every card filter")]
+    pub verbose_card: bool,
+
+    #[arg(long, help = "List the card filters and their configuration and exit")]
+    pub list_filters: bool,
+
+    #[arg(
+        long,
+        help = "Post a card first released on today's month and day in any year"
+    )]
+    pub on_this_day: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Export the formatted texts of the scryfall or oracle ids in FILE as JSON and exit"
+    )]
+    pub export_ids: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "export_ids",
+        help = "Write the export to FILE instead of stdout"
+    )]
+    pub export_output: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Read the environment variables from FILE instead of .env"
+    )]
+    pub env_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        requires = "json_card",
+        help = "Only format the card of --json-card without any network calls, print it and exit"
+    )]
+    pub format_only: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "format_only",
+        help = "Read the card for --format-only as scryfall JSON from FILE, - reads stdin"
+    )]
+    pub json_card: Option<PathBuf>,
 }
 
 impl CLIConfig {
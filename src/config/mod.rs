@@ -4,35 +4,234 @@
  * SPDX-License-Identifier: MIT
  */
 
+#[cfg(test)]
+mod builder;
 pub mod cli_config;
 
 use crate::error::{Error, Result};
+use crate::format::{FormatOptions, DEFAULT_ARTIST_TEMPLATE, DEFAULT_FACE_SEPARATOR};
+use crate::telegram::DEFAULT_TELEGRAM_CAPTION_TEMPLATE;
 
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Utc, Weekday};
 use dotenv::dotenv;
 use log::{debug, error};
+use scryfall::card::Layout;
+use scryfall::set::SetType;
+use std::collections::HashMap;
+use std::path::Path;
 use std::process;
+use std::time::Duration;
 use uuid::Uuid;
 
+#[cfg(test)]
+pub use crate::config::builder::DailyScryConfigBuilder;
+
+/// HTTP requests time out after this many seconds, unless `DAILY_SCRY_HTTP_TIMEOUT_SECS` is set
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
+
 #[derive(Debug)]
 pub struct DailyScryConfig {
+    pub enable_mastodon: bool,
+    pub enable_telegram: bool,
+    pub safe_mode: bool,
+    pub skip_misconfigured_backends: bool,
     pub mastodon_url: Option<String>,
     pub mastodon_access_token: Option<String>,
     pub mastodon_character_limit: Option<usize>,
+    pub mastodon_spoiler_text: String,
+    pub mana_symbols: Option<String>,
+    pub mana_emoji_path: Option<String>,
+    pub mastodon_reply_visibility: Option<String>,
+    pub client_name: String,
     pub telegram_token: Option<String>,
     pub telegram_chat_id: Option<String>,
+    pub telegram_thread_id: Option<String>,
+    pub max_thread_posts: Option<usize>,
+    pub max_faces: Option<usize>,
+    pub thread_numbering: bool,
     pub telegram_character_limit: Option<usize>,
+    pub telegram_send_as_document: bool,
+    pub emphasize_ability_words: bool,
+    pub telegram_hashtags: Vec<String>,
+    pub telegram_link_in_body: bool,
+    pub telegram_caption_template: String,
+    pub telegram_describe_image: bool,
+    pub post_header: Option<String>,
+    pub use_printed_text: bool,
+    pub timezone: Option<String>,
     pub image_path: String,
+    pub fallback_image: Option<String>,
     pub ignored_oracle_ids: Option<Vec<Uuid>>,
+    pub preset: Option<String>,
+    pub allowed_layouts: Option<Vec<Layout>>,
+    pub exclude_funny: bool,
+    pub allowed_set_types: Option<Vec<SetType>>,
+    pub excluded_types: Vec<String>,
+    pub strict_layouts: bool,
+    pub skip_empty_faces: bool,
+    pub combine_face_text: bool,
+    pub allow_no_oracle_id: bool,
+    pub require_flavor_text: bool,
+    pub require_highres: bool,
+    pub min_oracle_len: Option<usize>,
+    pub max_oracle_len: Option<usize>,
+    pub min_printings: Option<usize>,
+    pub min_power: Option<usize>,
+    pub max_power: Option<usize>,
+    pub min_toughness: Option<usize>,
+    pub max_toughness: Option<usize>,
+    pub power_toughness_creatures_only: bool,
+    pub include_variable_power_toughness: bool,
+    pub allowed_artists: Vec<String>,
+    pub excluded_artists: Vec<String>,
+    pub excluded_keywords: Vec<String>,
+    pub exclude_keywords_in_oracle_text: bool,
+    pub weekday_queries: Option<HashMap<Weekday, String>>,
+    pub weekday_hashtags: Option<HashMap<Weekday, Vec<String>>>,
+    pub show_price: Option<String>,
+    pub year_hashtag: bool,
+    pub artist_hashtags: bool,
+    pub show_watermark: bool,
+    pub show_story_spotlight: bool,
+    pub show_edhrec_rank: bool,
+    pub show_rulings_count: bool,
+    pub show_oracle_id: bool,
+    pub rarity_emoji: bool,
+    pub alt_text_include_set: bool,
+    pub include_tokens: bool,
+    pub render_text_image: bool,
+    pub teaser: bool,
+    pub reveal_delay_secs: Option<usize>,
+    pub decklist_path: Option<String>,
+    pub commander: Option<String>,
+    pub prefer_full_art: bool,
+    pub prefer_finish: Option<String>,
+    pub artist_template: String,
+    pub join_artists: String,
+    pub face_separator: String,
+    pub image_version: String,
+    pub image_cache: bool,
+    pub include_card_back: bool,
+    pub http_timeout_secs: Option<u64>,
+    pub request_delay_ms: Option<usize>,
+    pub max_attempts: Option<usize>,
+    pub max_image_dimension: Option<usize>,
+    pub min_image_width: Option<usize>,
     pub version: String,
 }
 
+/// The configuration [`DailyScryConfig::from_env_file()`] loads if no environment variable is set
+impl Default for DailyScryConfig {
+    fn default() -> Self {
+        DailyScryConfig {
+            enable_mastodon: false,
+            enable_telegram: false,
+            safe_mode: false,
+            skip_misconfigured_backends: false,
+            mastodon_url: None,
+            mastodon_access_token: None,
+            mastodon_character_limit: Some(500),
+            mastodon_spoiler_text: "Content Warning".to_owned(),
+            mana_symbols: None,
+            mana_emoji_path: None,
+            mastodon_reply_visibility: None,
+            client_name: "DailyScry".to_owned(),
+            telegram_token: None,
+            telegram_chat_id: None,
+            telegram_thread_id: None,
+            max_thread_posts: None,
+            max_faces: None,
+            thread_numbering: false,
+            telegram_character_limit: Some(4096),
+            telegram_send_as_document: false,
+            emphasize_ability_words: false,
+            telegram_hashtags: vec![],
+            telegram_link_in_body: false,
+            telegram_caption_template: DEFAULT_TELEGRAM_CAPTION_TEMPLATE.to_owned(),
+            telegram_describe_image: false,
+            post_header: None,
+            use_printed_text: false,
+            timezone: None,
+            image_path: String::from("/tmp"),
+            fallback_image: None,
+            ignored_oracle_ids: Some(vec![]),
+            preset: None,
+            allowed_layouts: Some(vec![]),
+            exclude_funny: false,
+            allowed_set_types: Some(vec![]),
+            excluded_types: vec![],
+            strict_layouts: false,
+            skip_empty_faces: false,
+            combine_face_text: false,
+            allow_no_oracle_id: true,
+            require_flavor_text: false,
+            require_highres: false,
+            min_oracle_len: None,
+            max_oracle_len: None,
+            min_printings: None,
+            min_power: None,
+            max_power: None,
+            min_toughness: None,
+            max_toughness: None,
+            power_toughness_creatures_only: false,
+            include_variable_power_toughness: false,
+            allowed_artists: vec![],
+            excluded_artists: vec![],
+            excluded_keywords: vec![],
+            exclude_keywords_in_oracle_text: false,
+            weekday_queries: Some(HashMap::new()),
+            weekday_hashtags: Some(HashMap::new()),
+            show_price: None,
+            year_hashtag: false,
+            artist_hashtags: false,
+            show_watermark: false,
+            show_story_spotlight: false,
+            show_edhrec_rank: false,
+            show_rulings_count: false,
+            show_oracle_id: false,
+            rarity_emoji: false,
+            alt_text_include_set: false,
+            include_tokens: false,
+            render_text_image: false,
+            teaser: false,
+            reveal_delay_secs: None,
+            decklist_path: None,
+            commander: None,
+            prefer_full_art: false,
+            prefer_finish: None,
+            artist_template: DEFAULT_ARTIST_TEMPLATE.to_owned(),
+            join_artists: "ampersand".to_owned(),
+            face_separator: DEFAULT_FACE_SEPARATOR.to_owned(),
+            image_version: "png".to_owned(),
+            image_cache: false,
+            include_card_back: false,
+            http_timeout_secs: Some(DEFAULT_HTTP_TIMEOUT_SECS),
+            request_delay_ms: None,
+            max_attempts: None,
+            max_image_dimension: None,
+            min_image_width: None,
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+        }
+    }
+}
+
 impl DailyScryConfig {
+    #[cfg(test)]
     pub fn new() -> Self {
-        dotenv().ok();
+        DailyScryConfig::from_env_file(None)
+    }
+
+    /// Loads the configuration, reading `env_file` instead of the `.env` in the working directory
+    /// if it is given
+    pub fn from_env_file(env_file: Option<&Path>) -> Self {
+        if let Err(error) = load_env_file(env_file) {
+            error!("Encountered error: {}", error);
+            process::exit(error.exit_code())
+        }
         return match DailyScryConfig::load_config() {
             Err(error) => {
                 error!("Encountered error: {}", error);
-                process::exit(1)
+                process::exit(error.exit_code())
             }
             Ok(config) => {
                 debug!("Config parsed {:?}", config);
@@ -46,33 +245,240 @@ impl DailyScryConfig {
             std::env::var("DAILY_SCRY_IGNORED_ORACLE_IDS").unwrap_or("".to_owned());
         let oracle_ids_result: std::result::Result<Vec<Uuid>, _> = oracle_ids_env
             .split(",")
+            .map(|string_value| string_value.trim())
+            .filter(|string_value| !string_value.is_empty())
             .map(|string_value| Uuid::parse_str(string_value))
             .collect();
+        let layouts_env = std::env::var("DAILY_SCRY_ALLOWED_LAYOUTS").unwrap_or("".to_owned());
+        let layouts_result: Option<Vec<Layout>> = layouts_env
+            .split(",")
+            .map(|string_value| string_value.trim())
+            .filter(|string_value| !string_value.is_empty())
+            .map(|string_value| parse_layout(&string_value.to_lowercase()))
+            .collect();
+        let set_types_env = std::env::var("DAILY_SCRY_ALLOWED_SET_TYPES").unwrap_or("".to_owned());
+        let set_types_result: Option<Vec<SetType>> = set_types_env
+            .split(",")
+            .map(|string_value| string_value.trim())
+            .filter(|string_value| !string_value.is_empty())
+            .map(|string_value| parse_set_type(&string_value.to_lowercase()))
+            .collect();
+        let preset = std::env::var("DAILY_SCRY_PRESET")
+            .ok()
+            .map(|value| value.trim().to_lowercase())
+            .filter(|value| !value.is_empty());
+        let (preset_layouts, preset_types) = preset
+            .as_deref()
+            .and_then(preset_defaults)
+            .unwrap_or_default();
         return Ok(DailyScryConfig {
+            enable_mastodon: read_bool("DAILY_SCRY_ENABLE_MASTODON", false),
+            enable_telegram: read_bool("DAILY_SCRY_ENABLE_TELEGRAM", false),
+            safe_mode: read_bool("DAILY_SCRY_SAFE_MODE", false),
+            skip_misconfigured_backends: read_bool("DAILY_SCRY_SKIP_MISCONFIGURED_BACKENDS", false),
             mastodon_url: std::env::var("DAILY_SCRY_MASTODON_URL").ok(),
-            mastodon_access_token: std::env::var("DAILY_SCRY_MASTODON_ACCESS_TOKEN").ok(),
+            mastodon_access_token: read_secret("DAILY_SCRY_MASTODON_ACCESS_TOKEN")?,
             mastodon_character_limit: std::env::var("DAILY_SCRY_MASTODON_CHARCTER_LIMIT")
                 .unwrap_or("500".to_owned())
                 .parse()
                 .ok(),
-            telegram_token: std::env::var("DAILY_SCRY_TELEGRAM_TOKEN").ok(),
+            mastodon_spoiler_text: std::env::var("DAILY_SCRY_MASTODON_SPOILER_TEXT")
+                .unwrap_or("Content Warning".to_owned()),
+            mana_symbols: std::env::var("DAILY_SCRY_MANA_SYMBOLS")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .filter(|value| !value.is_empty()),
+            mana_emoji_path: std::env::var("DAILY_SCRY_MANA_EMOJI_PATH")
+                .ok()
+                .filter(|value| !value.trim().is_empty()),
+            mastodon_reply_visibility: std::env::var("DAILY_SCRY_MASTODON_REPLY_VISIBILITY")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .filter(|value| !value.is_empty()),
+            client_name: std::env::var("DAILY_SCRY_CLIENT_NAME").unwrap_or("DailyScry".to_owned()),
+            telegram_token: read_secret("DAILY_SCRY_TELEGRAM_TOKEN")?,
             telegram_chat_id: std::env::var("DAILY_SCRY_TELEGRAM_CHAT_ID").ok(),
+            telegram_thread_id: std::env::var("DAILY_SCRY_TELEGRAM_THREAD_ID").ok(),
+            max_thread_posts: read_usize("DAILY_SCRY_MAX_THREAD_POSTS")?,
+            max_faces: read_usize("DAILY_SCRY_MAX_FACES")?,
+            thread_numbering: read_bool("DAILY_SCRY_THREAD_NUMBERING", false),
             telegram_character_limit: std::env::var("DAILY_SCRY_TELEGRAM_CHARCTER_LIMIT")
                 .unwrap_or("4096".to_owned())
                 .parse()
                 .ok(),
+            telegram_send_as_document: read_bool("DAILY_SCRY_TELEGRAM_SEND_AS_DOCUMENT", false),
+            emphasize_ability_words: read_bool("DAILY_SCRY_EMPHASIZE_ABILITY_WORDS", false),
+            telegram_hashtags: parse_hashtags(
+                &std::env::var("DAILY_SCRY_TELEGRAM_HASHTAGS").unwrap_or_default(),
+            ),
+            telegram_link_in_body: read_bool("DAILY_SCRY_TELEGRAM_LINK_IN_BODY", false),
+            telegram_caption_template: std::env::var("DAILY_SCRY_TELEGRAM_CAPTION_TEMPLATE")
+                .map(|value| unescape(&value))
+                .unwrap_or(DEFAULT_TELEGRAM_CAPTION_TEMPLATE.to_owned()),
+            telegram_describe_image: read_bool("DAILY_SCRY_TELEGRAM_DESCRIBE_IMAGE", false),
+            post_header: std::env::var("DAILY_SCRY_POST_HEADER")
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| unescape(&value)),
+            use_printed_text: read_bool("DAILY_SCRY_USE_PRINTED_TEXT", false),
+            timezone: std::env::var("DAILY_SCRY_TIMEZONE")
+                .ok()
+                .map(|value| value.trim().to_owned())
+                .filter(|value| !value.is_empty()),
             ignored_oracle_ids: if oracle_ids_env.is_empty() {
                 Some(vec![])
             } else {
                 oracle_ids_result.ok()
             },
+            allowed_layouts: if layouts_env.is_empty() {
+                Some(preset_layouts)
+            } else {
+                layouts_result
+            },
+            exclude_funny: read_bool("DAILY_SCRY_EXCLUDE_FUNNY", false),
+            allowed_set_types: set_types_result,
+            excluded_types: match std::env::var("DAILY_SCRY_EXCLUDED_TYPES") {
+                Ok(_) => read_lowercase_list("DAILY_SCRY_EXCLUDED_TYPES"),
+                Err(_) => preset_types,
+            },
+            preset,
+            strict_layouts: read_bool("DAILY_SCRY_STRICT_LAYOUTS", false),
+            skip_empty_faces: read_bool("DAILY_SCRY_SKIP_EMPTY_FACES", false),
+            combine_face_text: read_bool("DAILY_SCRY_COMBINE_FACE_TEXT", false),
+            allow_no_oracle_id: read_bool("DAILY_SCRY_ALLOW_NO_ORACLE_ID", true),
+            require_flavor_text: read_bool("DAILY_SCRY_REQUIRE_FLAVOR_TEXT", false),
+            require_highres: read_bool("DAILY_SCRY_REQUIRE_HIGHRES", false),
+            min_oracle_len: read_usize("DAILY_SCRY_MIN_ORACLE_LEN")?,
+            max_oracle_len: read_usize("DAILY_SCRY_MAX_ORACLE_LEN")?,
+            min_printings: read_usize("DAILY_SCRY_MIN_PRINTINGS")?,
+            min_power: read_usize("DAILY_SCRY_MIN_POWER")?,
+            max_power: read_usize("DAILY_SCRY_MAX_POWER")?,
+            min_toughness: read_usize("DAILY_SCRY_MIN_TOUGHNESS")?,
+            max_toughness: read_usize("DAILY_SCRY_MAX_TOUGHNESS")?,
+            power_toughness_creatures_only: read_bool(
+                "DAILY_SCRY_POWER_TOUGHNESS_CREATURES_ONLY",
+                false,
+            ),
+            include_variable_power_toughness: read_bool(
+                "DAILY_SCRY_INCLUDE_VARIABLE_POWER_TOUGHNESS",
+                false,
+            ),
+            allowed_artists: read_lowercase_list("DAILY_SCRY_ALLOWED_ARTISTS"),
+            excluded_artists: read_lowercase_list("DAILY_SCRY_EXCLUDED_ARTISTS"),
+            excluded_keywords: read_lowercase_list("DAILY_SCRY_EXCLUDE_KEYWORDS"),
+            exclude_keywords_in_oracle_text: read_bool(
+                "DAILY_SCRY_EXCLUDE_KEYWORDS_IN_ORACLE_TEXT",
+                false,
+            ),
+            weekday_queries: parse_weekday_map(
+                &std::env::var("DAILY_SCRY_WEEKDAY_QUERIES").unwrap_or("".to_owned()),
+            ),
+            weekday_hashtags: parse_weekday_hashtags(
+                &std::env::var("DAILY_SCRY_WEEKDAY_HASHTAGS").unwrap_or("".to_owned()),
+            ),
+            show_price: std::env::var("DAILY_SCRY_SHOW_PRICE")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .filter(|value| !value.is_empty()),
+            decklist_path: std::env::var("DAILY_SCRY_DECKLIST_PATH")
+                .ok()
+                .filter(|value| !value.trim().is_empty()),
+            commander: std::env::var("DAILY_SCRY_COMMANDER")
+                .ok()
+                .map(|value| value.trim().to_owned())
+                .filter(|value| !value.is_empty()),
+            year_hashtag: read_bool("DAILY_SCRY_YEAR_HASHTAG", false),
+            artist_hashtags: read_bool("DAILY_SCRY_ARTIST_HASHTAGS", false),
+            show_watermark: read_bool("DAILY_SCRY_SHOW_WATERMARK", false),
+            show_story_spotlight: read_bool("DAILY_SCRY_SHOW_STORY_SPOTLIGHT", false),
+            show_edhrec_rank: read_bool("DAILY_SCRY_SHOW_EDHREC_RANK", false),
+            show_rulings_count: read_bool("DAILY_SCRY_SHOW_RULINGS_COUNT", false),
+            show_oracle_id: read_bool("DAILY_SCRY_SHOW_ORACLE_ID", false),
+            rarity_emoji: read_bool("DAILY_SCRY_RARITY_EMOJI", false),
+            alt_text_include_set: read_bool("DAILY_SCRY_ALT_TEXT_INCLUDE_SET", false),
+            include_tokens: read_bool("DAILY_SCRY_INCLUDE_TOKENS", false),
+            render_text_image: read_bool("DAILY_SCRY_RENDER_TEXT_IMAGE", false),
+            teaser: read_bool("DAILY_SCRY_TEASER", false),
+            reveal_delay_secs: read_usize("DAILY_SCRY_REVEAL_DELAY_SECS")?,
+            face_separator: std::env::var("DAILY_SCRY_FACE_SEPARATOR")
+                .map(|value| unescape(&value))
+                .unwrap_or(DEFAULT_FACE_SEPARATOR.to_owned()),
+            prefer_full_art: read_bool("DAILY_SCRY_PREFER_FULL_ART", false),
+            prefer_finish: std::env::var("DAILY_SCRY_PREFER_FINISH")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .filter(|value| !value.is_empty()),
+            artist_template: std::env::var("DAILY_SCRY_ARTIST_TEMPLATE")
+                .unwrap_or(DEFAULT_ARTIST_TEMPLATE.to_owned()),
+            join_artists: std::env::var("DAILY_SCRY_JOIN_ARTISTS")
+                .map(|value| value.trim().to_lowercase())
+                .unwrap_or("ampersand".to_owned()),
+            image_version: std::env::var("DAILY_SCRY_IMAGE_VERSION")
+                .map(|value| value.trim().to_lowercase())
+                .unwrap_or("png".to_owned()),
+            image_cache: read_bool("DAILY_SCRY_IMAGE_CACHE", false),
+            include_card_back: read_bool("DAILY_SCRY_INCLUDE_CARD_BACK", false),
+            max_image_dimension: read_usize("DAILY_SCRY_MAX_IMAGE_DIMENSION")?,
+            min_image_width: read_usize("DAILY_SCRY_MIN_IMAGE_WIDTH")?,
+            http_timeout_secs: std::env::var("DAILY_SCRY_HTTP_TIMEOUT_SECS")
+                .unwrap_or(DEFAULT_HTTP_TIMEOUT_SECS.to_string())
+                .parse()
+                .ok(),
+            request_delay_ms: read_usize("DAILY_SCRY_REQUEST_DELAY_MS")?,
+            max_attempts: read_usize("DAILY_SCRY_MAX_ATTEMPTS")?,
             image_path: String::from("/tmp"),
+            fallback_image: std::env::var("DAILY_SCRY_FALLBACK_IMAGE")
+                .ok()
+                .filter(|value| !value.trim().is_empty()),
             version: env!("CARGO_PKG_VERSION").to_owned(),
         });
     }
 
+    /// Returns today's date in `DAILY_SCRY_TIMEZONE`, or in the local timezone if it isn't set
+    pub fn today(&self) -> NaiveDate {
+        date_in_timezone(self.timezone.as_deref(), Utc::now())
+    }
+
+    /// The timeout of HTTP requests, see `DAILY_SCRY_HTTP_TIMEOUT_SECS`
+    pub fn http_timeout(&self) -> Duration {
+        Duration::from_secs(self.http_timeout_secs.unwrap_or(DEFAULT_HTTP_TIMEOUT_SECS))
+    }
+
+    pub fn format_options(&self) -> FormatOptions {
+        FormatOptions {
+            artist_template: self.artist_template.clone(),
+            join_artists_with_and: self.join_artists == "and",
+            face_separator: self.face_separator.clone(),
+            rarity_emoji: self.rarity_emoji,
+            max_faces: self.max_faces,
+            printed_type_line: self.use_printed_text,
+        }
+    }
+
     pub fn validate(&self) -> Result<()> {
         self.check_oracle_ids()?;
+        self.check_preset()?;
+        self.check_allowed_layouts()?;
+        self.check_allowed_set_types()?;
+        self.check_weekday_queries()?;
+        self.check_weekday_hashtags()?;
+        self.check_show_price()?;
+        self.check_mana_symbols()?;
+        self.check_prefer_finish()?;
+        self.check_mastodon_reply_visibility()?;
+        self.check_oracle_len()?;
+        self.check_power_toughness()?;
+        self.check_artist_template()?;
+        self.check_join_artists()?;
+        self.check_image_version()?;
+        self.check_http_timeout()?;
+        self.check_timezone()?;
+        self.check_max_image_dimension()?;
+        self.check_min_image_width()?;
+        self.check_fallback_image()?;
+        self.check_max_thread_posts()?;
+        self.check_max_faces()?;
+        self.check_max_attempts()?;
         Ok(())
     }
 
@@ -86,6 +492,274 @@ impl DailyScryConfig {
         Ok(())
     }
 
+    fn check_preset(&self) -> Result<()> {
+        if self
+            .preset
+            .as_deref()
+            .is_some_and(|preset| preset_defaults(preset).is_none())
+        {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_PRESET".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_allowed_layouts(&self) -> Result<()> {
+        if self.allowed_layouts.is_none() {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_ALLOWED_LAYOUTS".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_allowed_set_types(&self) -> Result<()> {
+        if self.allowed_set_types.is_none() {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_ALLOWED_SET_TYPES".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_weekday_queries(&self) -> Result<()> {
+        if self.weekday_queries.is_none() {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_WEEKDAY_QUERIES".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_weekday_hashtags(&self) -> Result<()> {
+        if self.weekday_hashtags.is_none() {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_WEEKDAY_HASHTAGS".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_show_price(&self) -> Result<()> {
+        if let Some(currency) = &self.show_price {
+            if !["usd", "eur", "tix"].contains(&currency.as_str()) {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_SHOW_PRICE".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_mana_symbols(&self) -> Result<()> {
+        if let Some(mana_symbols) = &self.mana_symbols {
+            if !["text", "custom_emoji"].contains(&mana_symbols.as_str()) {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_MANA_SYMBOLS".to_string(),
+                });
+            }
+        }
+
+        if self
+            .mana_emoji_path
+            .as_ref()
+            .is_some_and(|mana_emoji_path| !Path::new(mana_emoji_path).is_file())
+        {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_MANA_EMOJI_PATH".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_prefer_finish(&self) -> Result<()> {
+        if let Some(finish) = &self.prefer_finish {
+            if !["nonfoil", "foil", "etched"].contains(&finish.as_str()) {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_PREFER_FINISH".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_mastodon_reply_visibility(&self) -> Result<()> {
+        if let Some(visibility) = &self.mastodon_reply_visibility {
+            if !["public", "unlisted", "private", "direct"].contains(&visibility.as_str()) {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_MASTODON_REPLY_VISIBILITY".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_oracle_len(&self) -> Result<()> {
+        if let (Some(min), Some(max)) = (self.min_oracle_len, self.max_oracle_len) {
+            if min > max {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_MIN_ORACLE_LEN".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_power_toughness(&self) -> Result<()> {
+        if let (Some(min), Some(max)) = (self.min_power, self.max_power) {
+            if min > max {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_MIN_POWER".to_string(),
+                });
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_toughness, self.max_toughness) {
+            if min > max {
+                return Err(Error::ReadConfiguration {
+                    key: "DAILY_SCRY_MIN_TOUGHNESS".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_artist_template(&self) -> Result<()> {
+        if !self.artist_template.contains("{artist}") {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_ARTIST_TEMPLATE".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_join_artists(&self) -> Result<()> {
+        if !["ampersand", "and"].contains(&self.join_artists.as_str()) {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_JOIN_ARTISTS".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_image_version(&self) -> Result<()> {
+        if !["png", "auto"].contains(&self.image_version.as_str()) {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_IMAGE_VERSION".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_http_timeout(&self) -> Result<()> {
+        if self.http_timeout_secs.is_none() {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_HTTP_TIMEOUT_SECS".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_timezone(&self) -> Result<()> {
+        if self
+            .timezone
+            .as_deref()
+            .is_some_and(|timezone| parse_timezone(timezone).is_none())
+        {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_TIMEZONE".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_max_attempts(&self) -> Result<()> {
+        if self.max_attempts == Some(0) {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_MAX_ATTEMPTS".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_max_image_dimension(&self) -> Result<()> {
+        if self.max_image_dimension == Some(0) {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_MAX_IMAGE_DIMENSION".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_min_image_width(&self) -> Result<()> {
+        let exceeds_max_dimension = match (self.min_image_width, self.max_image_dimension) {
+            (Some(min_width), Some(max_dimension)) => min_width > max_dimension,
+            _ => false,
+        };
+        if self.min_image_width == Some(0) || exceeds_max_dimension {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_MIN_IMAGE_WIDTH".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_fallback_image(&self) -> Result<()> {
+        if self
+            .fallback_image
+            .as_ref()
+            .is_some_and(|fallback_image| !Path::new(fallback_image).is_file())
+        {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_FALLBACK_IMAGE".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_max_thread_posts(&self) -> Result<()> {
+        if self
+            .max_thread_posts
+            .is_some_and(|max_thread_posts| max_thread_posts < 2)
+        {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_MAX_THREAD_POSTS".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_max_faces(&self) -> Result<()> {
+        if self.max_faces.is_some_and(|max_faces| max_faces == 0) {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_MAX_FACES".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn check_mastodon_config(&self) -> Result<()> {
         if self.mastodon_url.is_none() {
             return Err(Error::ReadConfiguration {
@@ -127,10 +801,227 @@ impl DailyScryConfig {
             });
         }
 
+        if self
+            .telegram_thread_id
+            .as_ref()
+            .is_some_and(|thread_id| thread_id.parse::<i32>().is_err())
+        {
+            return Err(Error::ReadConfiguration {
+                key: "DAILY_SCRY_TELEGRAM_THREAD_ID".to_string(),
+            });
+        }
+
         Ok(())
     }
 }
 
+fn parse_layout(value: &str) -> Option<Layout> {
+    match value {
+        "normal" => Some(Layout::Normal),
+        "split" => Some(Layout::Split),
+        "flip" => Some(Layout::Flip),
+        "transform" => Some(Layout::Transform),
+        "modal_dfc" => Some(Layout::ModalDfc),
+        "meld" => Some(Layout::Meld),
+        "leveler" => Some(Layout::Leveler),
+        "class" => Some(Layout::Class),
+        "case" => Some(Layout::Case),
+        "saga" => Some(Layout::Saga),
+        "adventure" => Some(Layout::Adventure),
+        "mutate" => Some(Layout::Mutate),
+        "prototype" => Some(Layout::Prototype),
+        "battle" => Some(Layout::Battle),
+        "planar" => Some(Layout::Planar),
+        "scheme" => Some(Layout::Scheme),
+        "vanguard" => Some(Layout::Vanguard),
+        "token" => Some(Layout::Token),
+        "double_faced_token" => Some(Layout::DoubleFacedToken),
+        "emblem" => Some(Layout::Emblem),
+        "augment" => Some(Layout::Augment),
+        "host" => Some(Layout::Host),
+        "art_series" => Some(Layout::ArtSeries),
+        "reversible_card" => Some(Layout::ReversibleCard),
+        _ => None,
+    }
+}
+
+/// Parses a timezone given as a fixed offset like `+02:00`, or `UTC`
+fn parse_timezone(value: &str) -> Option<FixedOffset> {
+    if value.eq_ignore_ascii_case("utc") {
+        return FixedOffset::east_opt(0);
+    }
+    value.parse().ok()
+}
+
+/// Returns the date of `now` in `timezone`, or in the local timezone if there is none
+fn date_in_timezone(timezone: Option<&str>, now: DateTime<Utc>) -> NaiveDate {
+    match timezone.and_then(parse_timezone) {
+        Some(offset) => now.with_timezone(&offset).date_naive(),
+        None => now.with_timezone(&Local).date_naive(),
+    }
+}
+
+/// Parses a scryfall set type like `expansion` or `masters`
+fn parse_set_type(value: &str) -> Option<SetType> {
+    serde_json::from_value(serde_json::Value::String(value.to_owned())).ok()
+}
+
+/// The layouts of cards that are used in regular games
+const GAME_LAYOUTS: [&str; 14] = [
+    "normal",
+    "split",
+    "flip",
+    "transform",
+    "modal_dfc",
+    "meld",
+    "leveler",
+    "class",
+    "case",
+    "saga",
+    "adventure",
+    "mutate",
+    "prototype",
+    "battle",
+];
+
+/// Returns the allowed layouts and excluded types `DAILY_SCRY_PRESET` fills in, if it is known
+///
+/// Both only apply if `DAILY_SCRY_ALLOWED_LAYOUTS` or `DAILY_SCRY_EXCLUDED_TYPES` are not set.
+fn preset_defaults(preset: &str) -> Option<(Vec<Layout>, Vec<String>)> {
+    let game_layouts = GAME_LAYOUTS
+        .iter()
+        .filter_map(|layout| parse_layout(layout))
+        .collect();
+    match preset {
+        "spells_only" => Some((game_layouts, vec!["basic".to_owned()])),
+        "permanents_only" => Some((
+            game_layouts,
+            vec!["instant".to_owned(), "sorcery".to_owned()],
+        )),
+        _ => None,
+    }
+}
+
+fn parse_weekday_map(value: &str) -> Option<HashMap<Weekday, String>> {
+    if value.trim().is_empty() {
+        return Some(HashMap::new());
+    }
+
+    let raw_queries: HashMap<String, String> = serde_json::from_str(value).ok()?;
+    raw_queries
+        .into_iter()
+        .map(|(weekday, query)| weekday.trim().parse().ok().map(|weekday| (weekday, query)))
+        .collect()
+}
+
+/// Parses a mapping of weekdays to hashtags seperated by spaces, a missing `#` is added
+fn parse_weekday_hashtags(value: &str) -> Option<HashMap<Weekday, Vec<String>>> {
+    let hashtags = parse_weekday_map(value)?
+        .into_iter()
+        .map(|(weekday, hashtags)| (weekday, parse_hashtags(&hashtags)))
+        .collect();
+    Some(hashtags)
+}
+
+/// Parses hashtags seperated by spaces, a missing `#` is added
+fn parse_hashtags(value: &str) -> Vec<String> {
+    value
+        .split_whitespace()
+        .map(|hashtag| format!("#{}", hashtag.trim_start_matches('#')))
+        .collect()
+}
+
+/// Sets the variables of `env_file` or of the `.env` in the working directory
+///
+/// Variables that are already set in the environment are not overridden. A missing `.env` is
+/// fine, a missing `env_file` is not.
+fn load_env_file(env_file: Option<&Path>) -> Result<()> {
+    match env_file {
+        Some(env_file) => {
+            dotenv::from_path(env_file).map_err(|_| Error::ReadConfiguration {
+                key: "--env-file".to_string(),
+            })?;
+        }
+        None => {
+            dotenv().ok();
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the escape sequences `\n`, `\t` and `\\` with the characters they stand for
+fn unescape(value: &str) -> String {
+    let mut result = String::new();
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            result.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => {
+                result.push('\\');
+                if other != '\\' {
+                    result.push(other);
+                }
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Reads a secret from `key` or, if that is not set, from the file at the path in `{key}_FILE`
+///
+/// This allows to mount secrets as files, e.g. with docker or kubernetes.
+fn read_secret(key: &str) -> Result<Option<String>> {
+    if let Ok(value) = std::env::var(key) {
+        return Ok(Some(value));
+    }
+    let file_key = format!("{}_FILE", key);
+    match std::env::var(&file_key) {
+        Ok(path) => std::fs::read_to_string(path)
+            .map(|value| Some(value.trim_end().to_owned()))
+            .map_err(|_| Error::ReadConfiguration { key: file_key }),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Reads a `,` separated list, e.g. of artists, lowercased for case-insensitive matching
+fn read_lowercase_list(key: &str) -> Vec<String> {
+    std::env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(|artist| artist.trim().to_lowercase())
+        .filter(|artist| !artist.is_empty())
+        .collect()
+}
+
+fn read_usize(key: &str) -> Result<Option<usize>> {
+    match std::env::var(key) {
+        Ok(value) if !value.trim().is_empty() => {
+            value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| Error::ReadConfiguration {
+                    key: key.to_string(),
+                })
+        }
+        _ => Ok(None),
+    }
+}
+
+fn read_bool(key: &str, default: bool) -> bool {
+    match std::env::var(key) {
+        Ok(value) if value.trim().eq_ignore_ascii_case("true") => true,
+        Ok(value) if value.trim().eq_ignore_ascii_case("false") => false,
+        _ => default,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,6 +1042,7 @@ mod tests {
                 ("DAILY_SCRY_TELEGRAM_TOKEN", Some(telegram_token)),
                 ("DAILY_SCRY_TELEGRAM_CHAT_ID", Some(telegram_chat_id)),
                 ("DAILY_SCRY_IGNORED_ORACLE_IDS", None),
+                ("DAILY_SCRY_STRICT_LAYOUTS", None),
             ],
             || {
                 let config = DailyScryConfig::load_config().unwrap();
@@ -161,6 +1053,7 @@ mod tests {
                 assert_eq!(config.mastodon_character_limit.unwrap(), 500);
                 assert_eq!(config.telegram_character_limit.unwrap(), 4096);
                 assert_eq!(config.ignored_oracle_ids.unwrap().len(), 0);
+                assert_eq!(config.strict_layouts, false);
             },
         );
     }
@@ -208,6 +1101,49 @@ mod tests {
             );
         }
 
+        fn parse_ignored_oracle_ids(value: &str) -> Option<Vec<Uuid>> {
+            temp_env::with_vars([("DAILY_SCRY_IGNORED_ORACLE_IDS", Some(value))], || {
+                DailyScryConfig::load_config().unwrap().ignored_oracle_ids
+            })
+        }
+
+        #[test]
+        fn test_oracle_ids_trailing_comma() {
+            assert_eq!(
+                parse_ignored_oracle_ids("bc71ebf6-2056-41f7-be35-b2e5c34afa99,"),
+                Some(vec![Uuid::parse_str(
+                    "bc71ebf6-2056-41f7-be35-b2e5c34afa99"
+                )
+                .unwrap()])
+            );
+        }
+
+        #[test]
+        fn test_oracle_ids_double_comma() {
+            assert_eq!(
+                parse_ignored_oracle_ids(
+                    "bc71ebf6-2056-41f7-be35-b2e5c34afa99,,b2c6aa39-2d2a-459c-a555-fb48ba993373"
+                ),
+                Some(vec![
+                    Uuid::parse_str("bc71ebf6-2056-41f7-be35-b2e5c34afa99").unwrap(),
+                    Uuid::parse_str("b2c6aa39-2d2a-459c-a555-fb48ba993373").unwrap()
+                ])
+            );
+        }
+
+        #[test]
+        fn test_oracle_ids_surrounding_spaces() {
+            assert_eq!(
+                parse_ignored_oracle_ids(
+                    " bc71ebf6-2056-41f7-be35-b2e5c34afa99 , b2c6aa39-2d2a-459c-a555-fb48ba993373 "
+                ),
+                Some(vec![
+                    Uuid::parse_str("bc71ebf6-2056-41f7-be35-b2e5c34afa99").unwrap(),
+                    Uuid::parse_str("b2c6aa39-2d2a-459c-a555-fb48ba993373").unwrap()
+                ])
+            );
+        }
+
         #[test]
         fn test_inavlid_oracle_ids() {
             let mastodon_url = "test_mastodon_url";
@@ -236,10 +1172,909 @@ mod tests {
                 },
             );
         }
-    }
 
-    #[cfg(test)]
-    mod check_mastodon_config {
+        #[test]
+        fn test_allowed_layouts() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_IGNORED_ORACLE_IDS", None),
+                    ("DAILY_SCRY_ALLOWED_LAYOUTS", Some("normal, transform")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.validate().is_ok(), true);
+                    assert_eq!(
+                        config.allowed_layouts.unwrap(),
+                        vec![Layout::Normal, Layout::Transform]
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn test_allowed_layouts_trailing_comma() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_IGNORED_ORACLE_IDS", None),
+                    ("DAILY_SCRY_ALLOWED_LAYOUTS", Some("normal, Transform,")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.validate().is_ok(), true);
+                    assert_eq!(
+                        config.allowed_layouts.unwrap(),
+                        vec![Layout::Normal, Layout::Transform]
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn test_invalid_allowed_layouts() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_IGNORED_ORACLE_IDS", None),
+                    ("DAILY_SCRY_ALLOWED_LAYOUTS", Some("normal,not_a_layout")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.validate().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_allowed_set_types {
+        use super::super::*;
+
+        #[test]
+        fn test_allowed_set_types() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_IGNORED_ORACLE_IDS", None),
+                    (
+                        "DAILY_SCRY_ALLOWED_SET_TYPES",
+                        Some("core, Expansion,masters"),
+                    ),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.validate().is_ok(), true);
+                    assert_eq!(
+                        config.allowed_set_types.unwrap(),
+                        vec![SetType::Core, SetType::Expansion, SetType::Masters]
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn test_invalid_allowed_set_types() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_IGNORED_ORACLE_IDS", None),
+                    (
+                        "DAILY_SCRY_ALLOWED_SET_TYPES",
+                        Some("expansion,not_a_set_type"),
+                    ),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.validate().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_show_price {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars([("DAILY_SCRY_SHOW_PRICE", Some("EUR"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.show_price.clone().unwrap(), "eur");
+                assert_eq!(config.check_show_price().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_unknown_currency_fail() {
+            temp_env::with_vars([("DAILY_SCRY_SHOW_PRICE", Some("gold"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_show_price().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_mana_symbols {
+        use super::super::*;
+
+        #[test]
+        fn test_custom_emoji_works() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MANA_SYMBOLS", Some("Custom_Emoji")),
+                    ("DAILY_SCRY_MANA_EMOJI_PATH", None),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.mana_symbols.as_deref(), Some("custom_emoji"));
+                    assert_eq!(config.check_mana_symbols().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_unknown_mana_symbols_fail() {
+            temp_env::with_vars([("DAILY_SCRY_MANA_SYMBOLS", Some("unicode"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_mana_symbols().is_err(), true);
+            });
+        }
+
+        #[test]
+        fn test_missing_mana_emoji_file_fail() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MANA_SYMBOLS", Some("custom_emoji")),
+                    ("DAILY_SCRY_MANA_EMOJI_PATH", Some("/does/not/exist")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_mana_symbols().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_prefer_finish {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars([("DAILY_SCRY_PREFER_FINISH", Some("Etched"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.prefer_finish.clone().unwrap(), "etched");
+                assert_eq!(config.check_prefer_finish().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_unknown_finish_fail() {
+            temp_env::with_vars([("DAILY_SCRY_PREFER_FINISH", Some("glossy"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_prefer_finish().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_mastodon_reply_visibility {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [("DAILY_SCRY_MASTODON_REPLY_VISIBILITY", Some("Unlisted"))],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(
+                        config.mastodon_reply_visibility.clone().unwrap(),
+                        "unlisted"
+                    );
+                    assert_eq!(config.check_mastodon_reply_visibility().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_unknown_visibility_fail() {
+            temp_env::with_vars(
+                [("DAILY_SCRY_MASTODON_REPLY_VISIBILITY", Some("secret"))],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_mastodon_reply_visibility().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_oracle_len {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MIN_ORACLE_LEN", Some("10")),
+                    ("DAILY_SCRY_MAX_ORACLE_LEN", None),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.min_oracle_len, Some(10));
+                    assert_eq!(config.max_oracle_len, None);
+                    assert_eq!(config.check_oracle_len().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_not_a_number_fail() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_ORACLE_LEN", Some("long"))], || {
+                assert_eq!(DailyScryConfig::load_config().is_err(), true);
+            });
+        }
+
+        #[test]
+        fn test_min_greater_than_max_fail() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MIN_ORACLE_LEN", Some("200")),
+                    ("DAILY_SCRY_MAX_ORACLE_LEN", Some("100")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_oracle_len().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_power_toughness {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MIN_POWER", Some("5")),
+                    ("DAILY_SCRY_MAX_TOUGHNESS", Some("10")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.min_power, Some(5));
+                    assert_eq!(config.max_toughness, Some(10));
+                    assert_eq!(config.check_power_toughness().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_min_greater_than_max_fail() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MIN_TOUGHNESS", Some("5")),
+                    ("DAILY_SCRY_MAX_TOUGHNESS", Some("2")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_power_toughness().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod unescape {
+        use super::super::*;
+
+        #[test]
+        fn test_escape_sequences() {
+            assert_eq!(unescape("\\n———\\n"), "\n———\n");
+            assert_eq!(unescape("a\\tb"), "a\tb");
+            assert_eq!(unescape("a\\\\nb"), "a\\nb");
+            assert_eq!(unescape("a\\xb\\"), "a\\xb\\");
+        }
+
+        #[test]
+        fn test_face_separator() {
+            temp_env::with_vars([("DAILY_SCRY_FACE_SEPARATOR", Some("\\n//\\n"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.face_separator, "\n//\n");
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_artist_template {
+        use super::super::*;
+
+        #[test]
+        fn test_default() {
+            temp_env::with_vars([("DAILY_SCRY_ARTIST_TEMPLATE", None::<&str>)], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.artist_template, "Illustrated by {artist}");
+                assert_eq!(config.check_artist_template().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_missing_placeholder_fail() {
+            temp_env::with_vars(
+                [("DAILY_SCRY_ARTIST_TEMPLATE", Some("Illustriert von"))],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_artist_template().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_join_artists {
+        use super::super::*;
+
+        #[test]
+        fn test_defaults_to_ampersand() {
+            temp_env::with_vars([("DAILY_SCRY_JOIN_ARTISTS", None::<&str>)], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.join_artists, "ampersand");
+                assert_eq!(config.check_join_artists().is_ok(), true);
+                assert_eq!(config.format_options().join_artists_with_and, false);
+            });
+        }
+
+        #[test]
+        fn test_and_works() {
+            temp_env::with_vars([("DAILY_SCRY_JOIN_ARTISTS", Some("And"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_join_artists().is_ok(), true);
+                assert_eq!(config.format_options().join_artists_with_and, true);
+            });
+        }
+
+        #[test]
+        fn test_unknown_fail() {
+            temp_env::with_vars([("DAILY_SCRY_JOIN_ARTISTS", Some("comma"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_join_artists().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_image_version {
+        use super::super::*;
+
+        #[test]
+        fn test_defaults_to_png() {
+            temp_env::with_vars([("DAILY_SCRY_IMAGE_VERSION", None::<&str>)], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.image_version, "png");
+                assert_eq!(config.check_image_version().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_auto_works() {
+            temp_env::with_vars([("DAILY_SCRY_IMAGE_VERSION", Some("Auto"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.image_version, "auto");
+                assert_eq!(config.check_image_version().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_unknown_version_fail() {
+            temp_env::with_vars([("DAILY_SCRY_IMAGE_VERSION", Some("small"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_image_version().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_http_timeout {
+        use super::super::*;
+
+        #[test]
+        fn test_defaults_to_30_seconds() {
+            temp_env::with_vars([("DAILY_SCRY_HTTP_TIMEOUT_SECS", None::<&str>)], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.http_timeout_secs, Some(30));
+                assert_eq!(config.check_http_timeout().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_not_a_number_fail() {
+            temp_env::with_vars([("DAILY_SCRY_HTTP_TIMEOUT_SECS", Some("soon"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_http_timeout().is_err(), true);
+            });
+        }
+
+        #[test]
+        fn test_http_timeout_without_timeout() {
+            let mut config = DailyScryConfig::default();
+            config.http_timeout_secs = None;
+            assert_eq!(
+                config.http_timeout(),
+                Duration::from_secs(DEFAULT_HTTP_TIMEOUT_SECS)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_timezone {
+        use super::super::*;
+
+        #[test]
+        fn test_date_in_timezone() {
+            let now = "2024-04-01T23:30:00Z".parse::<DateTime<Utc>>().unwrap();
+            let date = |timezone| date_in_timezone(Some(timezone), now);
+
+            assert_eq!(date("UTC"), NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+            assert_eq!(date("+02:00"), NaiveDate::from_ymd_opt(2024, 4, 2).unwrap());
+            assert_eq!(date("-05:00"), NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+        }
+
+        #[test]
+        fn test_post_header_date() {
+            temp_env::with_vars(
+                [
+                    (
+                        "DAILY_SCRY_POST_HEADER",
+                        Some("🗓 MTG Card of the Day — {date}"),
+                    ),
+                    ("DAILY_SCRY_TIMEZONE", Some("+14:00")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_timezone().is_ok(), true);
+                    let today = Utc::now()
+                        .with_timezone(&FixedOffset::east_opt(14 * 3600).unwrap())
+                        .date_naive();
+                    assert_eq!(
+                        crate::format::post_header(
+                            config.post_header.as_deref().unwrap(),
+                            config.today()
+                        ),
+                        format!("🗓 MTG Card of the Day — {}", today.format("%Y-%m-%d"))
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn test_invalid_timezone_fail() {
+            temp_env::with_vars([("DAILY_SCRY_TIMEZONE", Some("Europe/Berlin"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_timezone().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod read_secret {
+        use super::super::*;
+
+        #[test]
+        fn test_from_file() {
+            let path = std::env::temp_dir().join("daily_scry_telegram_token");
+            std::fs::write(&path, "token_from_file\n").unwrap();
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_TELEGRAM_TOKEN", None),
+                    ("DAILY_SCRY_TELEGRAM_TOKEN_FILE", path.to_str()),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.telegram_token.unwrap(), "token_from_file");
+                },
+            );
+        }
+
+        #[test]
+        fn test_direct_takes_precedence() {
+            let path = std::env::temp_dir().join("daily_scry_mastodon_access_token");
+            std::fs::write(&path, "token_from_file").unwrap();
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MASTODON_ACCESS_TOKEN", Some("direct_token")),
+                    ("DAILY_SCRY_MASTODON_ACCESS_TOKEN_FILE", path.to_str()),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.mastodon_access_token.unwrap(), "direct_token");
+                },
+            );
+        }
+
+        #[test]
+        fn test_missing_file_fail() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_TELEGRAM_TOKEN", None),
+                    (
+                        "DAILY_SCRY_TELEGRAM_TOKEN_FILE",
+                        Some("/nonexistent/daily_scry_telegram_token"),
+                    ),
+                ],
+                || {
+                    assert!(matches!(
+                        DailyScryConfig::load_config(),
+                        Err(Error::ReadConfiguration { key }) if key == "DAILY_SCRY_TELEGRAM_TOKEN_FILE"
+                    ));
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod read_artists {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [
+                    (
+                        "DAILY_SCRY_ALLOWED_ARTISTS",
+                        Some("Rebecca Guay, John Avon,"),
+                    ),
+                    ("DAILY_SCRY_EXCLUDED_ARTISTS", None),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.allowed_artists, vec!["rebecca guay", "john avon"]);
+                    assert_eq!(config.excluded_artists, Vec::<String>::new());
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod read_excluded_keywords {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_EXCLUDE_KEYWORDS", Some("Mill, coin flip")),
+                    ("DAILY_SCRY_EXCLUDE_KEYWORDS_IN_ORACLE_TEXT", None),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.excluded_keywords, vec!["mill", "coin flip"]);
+                    assert_eq!(config.exclude_keywords_in_oracle_text, false);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_preset {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_PRESET", Some("spells_only")),
+                    ("DAILY_SCRY_ALLOWED_LAYOUTS", None),
+                    ("DAILY_SCRY_EXCLUDED_TYPES", None),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.allowed_layouts.as_ref().unwrap().len(), 14);
+                    assert_eq!(config.excluded_types, vec!["basic"]);
+                    assert_eq!(config.check_preset().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_explicit_variables_override_preset() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_PRESET", Some("permanents_only")),
+                    ("DAILY_SCRY_ALLOWED_LAYOUTS", Some("normal")),
+                    ("DAILY_SCRY_EXCLUDED_TYPES", Some("")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.allowed_layouts, Some(vec![Layout::Normal]));
+                    assert_eq!(config.excluded_types, Vec::<String>::new());
+                },
+            );
+        }
+
+        #[test]
+        fn test_unknown_preset() {
+            temp_env::with_vars([("DAILY_SCRY_PRESET", Some("lands_only"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert!(matches!(
+                    config.check_preset(),
+                    Err(Error::ReadConfiguration { .. })
+                ));
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_max_attempts {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MAX_ATTEMPTS", Some("20")),
+                    ("DAILY_SCRY_REQUEST_DELAY_MS", Some("100")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.max_attempts, Some(20));
+                    assert_eq!(config.request_delay_ms, Some(100));
+                    assert_eq!(config.check_max_attempts().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_zero() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_ATTEMPTS", Some("0"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert!(matches!(
+                    config.check_max_attempts(),
+                    Err(Error::ReadConfiguration { .. })
+                ));
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_max_thread_posts {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_THREAD_POSTS", Some("3"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.max_thread_posts, Some(3));
+                assert_eq!(config.check_max_thread_posts().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_less_than_two_fail() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_THREAD_POSTS", Some("1"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_max_thread_posts().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod load_env_file {
+        use super::super::*;
+
+        #[test]
+        fn test_env_file() {
+            let env_file = std::env::temp_dir().join("daily_scry_test.env");
+            std::fs::write(
+                &env_file,
+                "DAILY_SCRY_MAX_FACES=3\nDAILY_SCRY_MAX_THREAD_POSTS=4\n",
+            )
+            .unwrap();
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MAX_FACES", None),
+                    ("DAILY_SCRY_MAX_THREAD_POSTS", Some("5")),
+                ],
+                || {
+                    load_env_file(Some(&env_file)).unwrap();
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.max_faces, Some(3));
+                    assert_eq!(config.max_thread_posts, Some(5));
+                },
+            );
+        }
+
+        #[test]
+        fn test_missing_env_file_fail() {
+            let env_file = Path::new("/daily_scry/missing.env");
+            assert_eq!(load_env_file(Some(env_file)).is_err(), true);
+        }
+    }
+
+    #[cfg(test)]
+    mod check_fallback_image {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            let fallback_image = std::env::temp_dir().join("daily_scry_fallback_config.png");
+            std::fs::write(&fallback_image, b"fallback").unwrap();
+            temp_env::with_vars(
+                [(
+                    "DAILY_SCRY_FALLBACK_IMAGE",
+                    Some(fallback_image.display().to_string()),
+                )],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_fallback_image().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_missing_file_fail() {
+            temp_env::with_vars(
+                [("DAILY_SCRY_FALLBACK_IMAGE", Some("/daily_scry/missing.png"))],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_fallback_image().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_max_faces {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_FACES", Some("2"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.max_faces, Some(2));
+                assert_eq!(config.check_max_faces().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_zero_fail() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_FACES", Some("0"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_max_faces().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_max_image_dimension {
+        use super::super::*;
+
+        #[test]
+        fn test_defaults_to_unlimited() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_IMAGE_DIMENSION", None::<&str>)], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.max_image_dimension, None);
+                assert_eq!(config.check_max_image_dimension().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_zero_fail() {
+            temp_env::with_vars([("DAILY_SCRY_MAX_IMAGE_DIMENSION", Some("0"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_max_image_dimension().is_err(), true);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod check_min_image_width {
+        use super::super::*;
+
+        #[test]
+        fn test_defaults_to_off() {
+            temp_env::with_vars([("DAILY_SCRY_MIN_IMAGE_WIDTH", None::<&str>)], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.min_image_width, None);
+                assert_eq!(config.check_min_image_width().is_ok(), true);
+            });
+        }
+
+        #[test]
+        fn test_zero_fail() {
+            temp_env::with_vars([("DAILY_SCRY_MIN_IMAGE_WIDTH", Some("0"))], || {
+                let config = DailyScryConfig::load_config().unwrap();
+                assert_eq!(config.check_min_image_width().is_err(), true);
+            });
+        }
+
+        #[test]
+        fn test_above_max_image_dimension_fail() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_MIN_IMAGE_WIDTH", Some("800")),
+                    ("DAILY_SCRY_MAX_IMAGE_DIMENSION", Some("500")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_min_image_width().is_err(), true);
+                },
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod parse_weekday_map {
+        use super::super::*;
+
+        #[test]
+        fn test_empty() {
+            assert_eq!(parse_weekday_map("").unwrap().len(), 0);
+        }
+
+        #[test]
+        fn test_works() {
+            let weekday_queries =
+                parse_weekday_map(r#"{"tuesday": "t:goblin", "Wed": "t:planeswalker"}"#).unwrap();
+            assert_eq!(weekday_queries.len(), 2);
+            assert_eq!(weekday_queries[&Weekday::Tue], "t:goblin");
+            assert_eq!(weekday_queries[&Weekday::Wed], "t:planeswalker");
+        }
+
+        #[test]
+        fn test_invalid_weekday() {
+            assert_eq!(
+                parse_weekday_map(r#"{"someday": "t:goblin"}"#).is_none(),
+                true
+            );
+        }
+
+        #[test]
+        fn test_invalid_json() {
+            assert_eq!(parse_weekday_map("tuesday=t:goblin").is_none(), true);
+        }
+    }
+
+    #[cfg(test)]
+    mod parse_hashtags {
+        use super::super::*;
+
+        #[test]
+        fn test_works() {
+            assert_eq!(
+                parse_hashtags(" #MagicTheGathering  DailyScry "),
+                vec!["#MagicTheGathering", "#DailyScry"]
+            );
+            assert_eq!(parse_hashtags(""), Vec::<String>::new());
+        }
+    }
+
+    #[cfg(test)]
+    mod parse_weekday_hashtags {
+        use super::super::*;
+
+        #[test]
+        fn test_empty() {
+            assert_eq!(parse_weekday_hashtags("").unwrap().len(), 0);
+        }
+
+        #[test]
+        fn test_works() {
+            let weekday_hashtags =
+                parse_weekday_hashtags(r##"{"thursday": "#ThrowbackThursday  MTGArt"}"##).unwrap();
+            assert_eq!(
+                weekday_hashtags[&Weekday::Thu],
+                vec!["#ThrowbackThursday", "#MTGArt"]
+            );
+        }
+
+        #[test]
+        fn test_invalid_weekday() {
+            assert_eq!(
+                parse_weekday_hashtags(r##"{"someday": "#ThrowbackThursday"}"##).is_none(),
+                true
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod check_mastodon_config {
         use super::super::*;
 
         #[test]
@@ -367,6 +2202,39 @@ mod tests {
             )
         }
 
+        #[test]
+        fn test_thread_id() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_TELEGRAM_TOKEN", Some("test_telegram_token")),
+                    ("DAILY_SCRY_TELEGRAM_CHAT_ID", Some("test_telegram_chat_id")),
+                    ("DAILY_SCRY_TELEGRAM_CHARCTER_LIMIT", Some("2")),
+                    ("DAILY_SCRY_TELEGRAM_THREAD_ID", Some("42")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.telegram_thread_id.clone().unwrap(), "42");
+                    assert_eq!(config.check_telegram_config().is_ok(), true);
+                },
+            );
+        }
+
+        #[test]
+        fn test_thread_id_fail() {
+            temp_env::with_vars(
+                [
+                    ("DAILY_SCRY_TELEGRAM_TOKEN", Some("test_telegram_token")),
+                    ("DAILY_SCRY_TELEGRAM_CHAT_ID", Some("test_telegram_chat_id")),
+                    ("DAILY_SCRY_TELEGRAM_CHARCTER_LIMIT", Some("2")),
+                    ("DAILY_SCRY_TELEGRAM_THREAD_ID", Some("general")),
+                ],
+                || {
+                    let config = DailyScryConfig::load_config().unwrap();
+                    assert_eq!(config.check_telegram_config().is_err(), true);
+                },
+            );
+        }
+
         #[test]
         fn test_token_fail() {
             let telegram_chat_id = "test_telegram_chat_id";
@@ -14,6 +14,11 @@ pub enum Error {
     #[snafu(display("Megalodon error {:?}", error))]
     MegalodonError { error: megalodon::error::Error },
 
+    #[snafu(display(
+        "Mastodon access token is invalid or expired, check DAILY_SCRY_MASTODON_ACCESS_TOKEN"
+    ))]
+    MastodonTokenInvalid,
+
     #[snafu(display("Teloxide error {:?}", error))]
     TeloxideError { error: teloxide_core::RequestError },
 
@@ -29,22 +34,62 @@ pub enum Error {
     #[snafu(display("Requested card layout {:?} is not known", layout))]
     UnknownCardLayout { layout: scryfall::card::Layout },
 
-    #[snafu(display("Unable to upload file {}", file_name))]
-    ImageUploadFailed { file_name: String },
+    #[snafu(display("Unable to upload file {}: {}", file_name, source))]
+    ImageUploadFailed {
+        file_name: String,
+        source: megalodon::error::Error,
+    },
 
     #[snafu(display("Can't rotate image"))]
     ImageRotationFailed,
+
+    #[snafu(display("Can't render the card text to an image"))]
+    TextImageFailed,
+
+    #[snafu(display("'{}' is not a scryfall or oracle id", id))]
+    InvalidCardId { id: String },
+
+    #[snafu(display("HTTP request timed out"))]
+    Timeout,
+
+    #[snafu(display("HTTP request failed: {}", error))]
+    RequestFailed { error: reqwest::Error },
+
+    #[snafu(display("Unable to read or write a file: {}", error))]
+    FileError { error: std::io::Error },
+
+    #[snafu(display("Unable to serialize JSON: {}", error))]
+    JsonError { error: serde_json::Error },
+
+    #[snafu(display(
+        "No card matched your filters after {} attempts; loosen the filters or raise DAILY_SCRY_MAX_ATTEMPTS",
+        attempts
+    ))]
+    NoCardFound { attempts: usize },
 }
 
 impl From<reqwest::Error> for Error {
-    fn from(_: reqwest::Error) -> Self {
-        Error::ImageNotFound
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            return Error::Timeout;
+        }
+        if error.status() == Some(reqwest::StatusCode::NOT_FOUND) {
+            return Error::ImageNotFound;
+        }
+        // connection errors, server errors and the like are worth retrying
+        Error::RequestFailed { error }
     }
 }
 
 impl From<std::io::Error> for Error {
-    fn from(_: std::io::Error) -> Self {
-        Error::ImageNotFound
+    fn from(error: std::io::Error) -> Self {
+        Error::FileError { error }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::JsonError { error }
     }
 }
 
@@ -56,12 +101,28 @@ impl From<scryfall::Error> for Error {
 
 impl From<megalodon::error::Error> for Error {
     fn from(error: megalodon::error::Error) -> Self {
+        if is_unauthorized(&error) {
+            return Error::MastodonTokenInvalid;
+        }
         Error::MegalodonError { error: error }
     }
 }
 
+/// Whether mastodon rejected a request because the access token is invalid or expired
+pub fn is_unauthorized(error: &megalodon::error::Error) -> bool {
+    match error {
+        megalodon::error::Error::OwnError(own_error) => own_error.status == Some(401),
+        _ => false,
+    }
+}
+
 impl From<teloxide_core::RequestError> for Error {
     fn from(error: teloxide_core::RequestError) -> Self {
+        if let teloxide_core::RequestError::Network(ref network_error) = error {
+            if network_error.is_timeout() {
+                return Error::Timeout;
+            }
+        }
         Error::TeloxideError { error: error }
     }
 }
@@ -72,4 +133,170 @@ impl From<image::ImageError> for Error {
     }
 }
 
+/// The process exits with this code if the configuration is invalid
+pub const EXIT_CODE_CONFIGURATION: i32 = 2;
+/// The process exits with this code if talking to scryfall, mastodon or telegram failed
+pub const EXIT_CODE_NETWORK: i32 = 3;
+/// The process exits with this code if the selected card could not be formatted or its images
+/// could not be prepared
+pub const EXIT_CODE_CARD: i32 = 4;
+/// The process exits with this code if no card passed the card filters within
+/// `DAILY_SCRY_MAX_ATTEMPTS`
+pub const EXIT_CODE_NO_CARD_FOUND: i32 = 5;
+/// The process exits with this code if a local file could not be read or written, e.g. because
+/// the disk is full
+pub const EXIT_CODE_FILE: i32 = 6;
+
+impl Error {
+    /// Returns the exit code of the process for this error
+    ///
+    /// Network errors are usually transient and worth retrying, while configuration errors are
+    /// not.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::ReadConfiguration { .. } | Error::MastodonTokenInvalid => {
+                EXIT_CODE_CONFIGURATION
+            }
+            Error::ScryfallError { .. }
+            | Error::MegalodonError { .. }
+            | Error::TeloxideError { .. }
+            | Error::ImageUploadFailed { .. }
+            | Error::Timeout
+            | Error::RequestFailed { .. } => EXIT_CODE_NETWORK,
+            Error::ImageNotFound
+            | Error::TextNotFound
+            | Error::UnknownCardLayout { .. }
+            | Error::ImageRotationFailed
+            | Error::TextImageFailed
+            | Error::InvalidCardId { .. }
+            | Error::JsonError { .. } => EXIT_CODE_CARD,
+            Error::NoCardFound { .. } => EXIT_CODE_NO_CARD_FOUND,
+            Error::FileError { .. } => EXIT_CODE_FILE,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+
+    /// Returns the error of a request to a server that answers with `status`
+    async fn status_error(status: &str) -> reqwest::Error {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\n\r\n", status);
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        reqwest::get(url)
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap_err()
+    }
+
+    #[test]
+    fn test_exit_code_configuration() {
+        let error = Error::ReadConfiguration {
+            key: "DAILY_SCRY_MASTODON_URL".to_owned(),
+        };
+        assert_eq!(error.exit_code(), EXIT_CODE_CONFIGURATION);
+    }
+
+    #[test]
+    fn test_exit_code_network() {
+        assert_eq!(Error::Timeout.exit_code(), EXIT_CODE_NETWORK);
+        let error = Error::ScryfallError {
+            error: scryfall::Error::Other("Not found".to_owned()),
+        };
+        assert_eq!(error.exit_code(), EXIT_CODE_NETWORK);
+    }
+
+    #[test]
+    fn test_megalodon_unauthorized_is_token_invalid() {
+        let error = Error::from(megalodon::error::Error::new_own(
+            "The access token is invalid".to_owned(),
+            megalodon::error::Kind::HTTPStatusError,
+            None,
+            Some(401),
+        ));
+        assert!(matches!(error, Error::MastodonTokenInvalid));
+        assert!(error
+            .to_string()
+            .contains("DAILY_SCRY_MASTODON_ACCESS_TOKEN"));
+        assert_eq!(error.exit_code(), EXIT_CODE_CONFIGURATION);
+
+        let error = Error::from(megalodon::error::Error::new_own(
+            "Internal Server Error".to_owned(),
+            megalodon::error::Kind::HTTPStatusError,
+            None,
+            Some(500),
+        ));
+        assert!(matches!(error, Error::MegalodonError { .. }));
+    }
+
+    #[test]
+    fn test_exit_code_card() {
+        assert_eq!(Error::ImageNotFound.exit_code(), EXIT_CODE_CARD);
+        assert_eq!(Error::TextImageFailed.exit_code(), EXIT_CODE_CARD);
+        let error = Error::InvalidCardId {
+            id: "grizzly bears".to_owned(),
+        };
+        assert_eq!(error.exit_code(), EXIT_CODE_CARD);
+        let error = Error::UnknownCardLayout {
+            layout: scryfall::card::Layout::Battle,
+        };
+        assert_eq!(error.exit_code(), EXIT_CODE_CARD);
+        let error = Error::from(serde_json::from_str::<serde_json::Value>("{").unwrap_err());
+        assert!(matches!(error, Error::JsonError { .. }));
+        assert_eq!(error.exit_code(), EXIT_CODE_CARD);
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_not_found_is_card_error() {
+        let error = Error::from(status_error("404 Not Found").await);
+        assert!(matches!(error, Error::ImageNotFound));
+        assert_eq!(error.exit_code(), EXIT_CODE_CARD);
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_server_error_is_network_error() {
+        let error = Error::from(status_error("503 Service Unavailable").await);
+        assert!(matches!(error, Error::RequestFailed { .. }));
+        assert_eq!(error.exit_code(), EXIT_CODE_NETWORK);
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_connect_error_is_network_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = Error::from(reqwest::get(url).await.unwrap_err());
+        assert!(matches!(error, Error::RequestFailed { .. }));
+        assert_eq!(error.exit_code(), EXIT_CODE_NETWORK);
+    }
+
+    #[test]
+    fn test_exit_code_file() {
+        let error = Error::from(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "permission denied",
+        ));
+        assert!(matches!(error, Error::FileError { .. }));
+        assert_eq!(error.exit_code(), EXIT_CODE_FILE);
+    }
+
+    #[test]
+    fn test_exit_code_no_card_found() {
+        let error = Error::NoCardFound { attempts: 10 };
+        assert_eq!(error.exit_code(), EXIT_CODE_NO_CARD_FOUND);
+        assert!(error
+            .to_string()
+            .starts_with("No card matched your filters after 10 attempts"));
+    }
+}
@@ -0,0 +1,24 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use scryfall::Card;
+use std::path::PathBuf;
+
+/// Returns the card of `tests/fixtures/{name}.json`, in the JSON scryfall returns
+///
+/// Tests that only format a card can use a fixture instead of fetching it, so they run without
+/// the scryfall API.
+///
+/// # Arguments
+///
+/// * `name` - The file name of the fixture without the extension, e.g. `grizzly_bears`
+pub fn card_fixture(name: &str) -> Card {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{}.json", name));
+    crate::read_json_card(&path).unwrap()
+}
@@ -4,11 +4,132 @@
  * SPDX-License-Identifier: MIT
  */
 
+use chrono::{Datelike, NaiveDate, Weekday};
 use log::trace;
-use scryfall::card::{Card, CardFace, Layout};
+use scryfall::card::{Card, CardFace, Layout, Rarity};
+use scryfall::ruling::Ruling;
+use std::collections::HashMap;
 use string_builder::Builder;
 
 use crate::error::{Error, Result};
+use crate::util::{limit_posts, split_text, Additional};
+
+/// The default artist line, `{artist}` is replaced by the name of the artist
+pub const DEFAULT_ARTIST_TEMPLATE: &str = "Illustrated by {artist}";
+
+/// The default separator between the faces of split, flip and adventure cards
+pub const DEFAULT_FACE_SEPARATOR: &str = "\n\n";
+
+/// The text of the teaser post, which must not give the card away
+pub const TEASER_TEXT: &str = "Can you guess today's card? It is revealed in the reply.";
+
+/// The description of the teaser image
+pub const TEASER_DESCRIPTION: &str = "The cropped art of today's card";
+
+/// Ability words that start a line of oracle text, followed by [`ABILITY_WORD_SEPARATOR`]
+///
+/// Scryfall doesn't flag ability words, so they are matched against this list.
+pub const ABILITY_WORDS: &[&str] = &[
+    "Adamant",
+    "Addendum",
+    "Alliance",
+    "Battalion",
+    "Bloodrush",
+    "Celebration",
+    "Channel",
+    "Chroma",
+    "Cohort",
+    "Constellation",
+    "Converge",
+    "Corrupted",
+    "Coven",
+    "Delirium",
+    "Descend 4",
+    "Descend 8",
+    "Domain",
+    "Eerie",
+    "Eminence",
+    "Enrage",
+    "Fateful hour",
+    "Fathomless descent",
+    "Ferocious",
+    "Formidable",
+    "Grandeur",
+    "Hellbent",
+    "Heroic",
+    "Imprint",
+    "Inspired",
+    "Join forces",
+    "Kinship",
+    "Landfall",
+    "Lieutenant",
+    "Magecraft",
+    "Metalcraft",
+    "Morbid",
+    "Pack tactics",
+    "Parley",
+    "Radiance",
+    "Raid",
+    "Rally",
+    "Revolt",
+    "Secret council",
+    "Spell mastery",
+    "Strive",
+    "Survival",
+    "Sweep",
+    "Tempting offer",
+    "Threshold",
+    "Undergrowth",
+    "Valiant",
+    "Will of the council",
+];
+
+/// The separator between an ability word and the rest of the line
+pub const ABILITY_WORD_SEPARATOR: &str = " — ";
+
+/// Returns the ability word `line` starts with, if any
+///
+/// # Arguments
+///
+/// * `line` - A line of a card text
+pub fn ability_word(line: &str) -> Option<&'static str> {
+    ABILITY_WORDS.iter().copied().find(|ability_word| {
+        line.strip_prefix(ability_word)
+            .is_some_and(|rest| rest.starts_with(ABILITY_WORD_SEPARATOR))
+    })
+}
+
+/// Options that change how cards are formatted
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    /// The artist line, `{artist}` is replaced by the name of the artist
+    pub artist_template: String,
+    /// The separator between faces that are combined into one text
+    pub face_separator: String,
+    /// Whether to prefix the names with an emoji for the rarity of the card
+    pub rarity_emoji: bool,
+    /// The maximum number of faces that are formatted, the rest is replaced by a note
+    pub max_faces: Option<usize>,
+    /// Whether the artists of collaborative art are joined with `and` instead of scryfall's `&`
+    pub join_artists_with_and: bool,
+    /// Whether the type line printed on a localized card is shown instead of the English one
+    ///
+    /// The English type line still decides how the card is formatted.
+    pub printed_type_line: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            artist_template: DEFAULT_ARTIST_TEMPLATE.to_owned(),
+            face_separator: DEFAULT_FACE_SEPARATOR.to_owned(),
+            rarity_emoji: false,
+            max_faces: None,
+            join_artists_with_and: false,
+            printed_type_line: false,
+        }
+    }
+}
 
 enum CardOrFace<'a> {
     Card(&'a Card),
@@ -20,6 +141,7 @@ enum CardOrFace<'a> {
 /// # Arguments
 ///
 /// * `card` - A borrowed [`scryfall::card::Card`]
+/// * `format_options` - The [`FormatOptions`] to use
 ///
 /// # Example
 ///
@@ -35,9 +157,9 @@ enum CardOrFace<'a> {
 ///
 /// Illustrated by Jeff A. Menges
 /// ```
-pub fn format_card(card: &Card) -> Result<Vec<String>> {
+pub fn format_card(card: &Card, format_options: &FormatOptions) -> Result<Vec<String>> {
     trace!("formatting card…");
-    match card.layout.clone() {
+    let card_texts = match card.layout.clone() {
         Layout::Normal
         | Layout::Meld
         | Layout::Leveler
@@ -52,31 +174,34 @@ pub fn format_card(card: &Card) -> Result<Vec<String>> {
         | Layout::Planar
         | Layout::Scheme
         | Layout::Vanguard
-        | Layout::Case => format_normal_layout(card),
+        | Layout::Case => format_normal_layout(card, format_options),
+        // the faces are collapsed before they are joined, so the face separator is kept as is
         Layout::Split | Layout::Flip | Layout::Adventure => {
-            format_single_image_multiple_faces_layout(card)
+            return format_single_image_multiple_faces_layout(card, format_options);
         }
         Layout::Transform
         | Layout::ModalDfc
         | Layout::ReversibleCard
         | Layout::DoubleFacedToken
-        | Layout::ArtSeries => format_multiple_faces_layout(card),
+        | Layout::ArtSeries => format_multiple_faces_layout(card, format_options),
         _ => Err(Error::UnknownCardLayout {
             layout: card.layout,
         }),
-    }
+    }?;
+    Ok(collapse_blank_lines(card_texts))
 }
 
-pub fn get_artist(card: &Card) -> Result<Option<String>> {
+pub fn get_artist(card: &Card, format_options: &FormatOptions) -> Result<Option<String>> {
     match card.layout.clone() {
         Layout::Transform
         | Layout::ModalDfc
         | Layout::ReversibleCard
         | Layout::DoubleFacedToken
         | Layout::ArtSeries => {
-            let faces = card.card_faces.clone().unwrap();
+            let faces = card.card_faces.clone().ok_or(Error::TextNotFound)?;
+            let first_face = faces.first().ok_or(Error::TextNotFound)?;
             let mut builder = Builder::default();
-            artist(&mut builder, &CardOrFace::Face(&faces[0]));
+            artist(&mut builder, &CardOrFace::Face(first_face), format_options);
             return builder
                 .string()
                 .map(|string| Some(string))
@@ -86,14 +211,451 @@ pub fn get_artist(card: &Card) -> Result<Option<String>> {
     }
 }
 
-fn format_normal_layout(card: &Card) -> Result<Vec<String>> {
+/// Returns the price of a [`scryfall::card::Card`] in the given currency, if scryfall knows one
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+/// * `currency` - One of `usd`, `eur` or `tix`
+///
+/// # Example
+///
+/// ```
+/// ~$3.50 (USD)
+/// ```
+pub fn get_price(card: &Card, currency: &str) -> Option<String> {
+    match currency {
+        "usd" => card
+            .prices
+            .usd
+            .clone()
+            .map(|price| format!("~${} (USD)", price)),
+        "eur" => card
+            .prices
+            .eur
+            .clone()
+            .map(|price| format!("~€{} (EUR)", price)),
+        "tix" => card
+            .prices
+            .tix
+            .clone()
+            .map(|price| format!("~{} (TIX)", price)),
+        _ => None,
+    }
+}
+
+/// Returns the set and collector number of a [`scryfall::card::Card`]
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+/// * `use_printed_text` - Whether to add the type line printed on a localized card, if it has one
+///
+/// # Example
+///
+/// ```
+/// from Limited Edition Alpha #232
+/// from Kaldheim #179 (Kreatur — Bär)
+/// ```
+pub fn get_provenance(card: &Card, use_printed_text: bool) -> String {
+    let provenance = format!("from {} #{}", card.set_name, card.collector_number);
+    match card.printed_type_line.as_ref().filter(|_| use_printed_text) {
+        Some(printed_type_line) => format!("{} ({})", provenance, printed_type_line),
+        None => provenance,
+    }
+}
+
+/// Returns the notes for a [`scryfall::card::Card`], each on its own line
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+/// * `show_story_spotlight` - Whether to add a note if the card is a story spotlight
+/// * `show_watermark` - Whether to add a note with the watermark of the card
+///
+/// # Example
+///
+/// ```
+/// ★ Story Spotlight
+/// Watermark: Selesnya
+/// ```
+pub fn get_notes(card: &Card, show_story_spotlight: bool, show_watermark: bool) -> Vec<String> {
+    let mut notes = vec![];
+    if show_story_spotlight && card.story_spotlight {
+        notes.push("★ Story Spotlight".to_owned());
+    }
+    if let Some(watermark) = card.watermark.clone().filter(|_| show_watermark) {
+        let mut characters = watermark.chars();
+        let watermark = match characters.next() {
+            Some(first) => first.to_uppercase().chain(characters).collect(),
+            None => watermark,
+        };
+        notes.push(format!("Watermark: {}", watermark));
+    }
+    notes
+}
+
+/// Returns how many rulings a card has and when the latest was published, if it has any
+///
+/// # Arguments
+///
+/// * `rulings` - The rulings of the card
+///
+/// # Example
+///
+/// ```
+/// 3 official rulings, the latest from 2024-04-02
+/// ```
+pub fn get_rulings_count(rulings: &[Ruling]) -> Option<String> {
+    let latest = rulings.iter().map(|ruling| ruling.published_at).max()?;
+    let noun = if rulings.len() == 1 {
+        "ruling"
+    } else {
+        "rulings"
+    };
+    Some(format!(
+        "{} official {}, the latest from {}",
+        rulings.len(),
+        noun,
+        latest.format("%Y-%m-%d")
+    ))
+}
+
+/// Returns the EDHREC rank of a [`scryfall::card::Card`], if it has one
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+///
+/// # Example
+///
+/// ```
+/// EDHREC rank: 1234
+/// ```
+pub fn get_edhrec_rank(card: &Card) -> Option<String> {
+    card.edhrec_rank
+        .map(|edhrec_rank| format!("EDHREC rank: {}", edhrec_rank))
+}
+
+/// Returns a line with the first eight characters of the oracle id of the card, if it has one
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+///
+/// # Example
+///
+/// ```
+/// oid: 56719f6a
+/// ```
+pub fn get_oracle_id(card: &Card) -> Option<String> {
+    card.oracle_id.map(|oracle_id| {
+        let oracle_id = oracle_id.to_string();
+        format!("oid: {}", &oracle_id[..8])
+    })
+}
+
+/// Returns a hashtag for the year the card was released in
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+///
+/// # Example
+///
+/// ```
+/// #MTG1993
+/// ```
+pub fn year_hashtag(card: &Card) -> String {
+    format!("#MTG{}", card.released_at.year())
+}
+
+/// Returns a hashtag for every artist of a [`scryfall::card::Card`], or of its first face
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+///
+/// # Example
+///
+/// ```
+/// #RebeccaGuay
+/// ```
+pub fn artist_hashtags(card: &Card) -> Vec<String> {
+    let artist = card.artist.clone().or_else(|| {
+        card.card_faces
+            .iter()
+            .flatten()
+            .find_map(|face| face.artist.clone())
+    });
+    artist
+        .iter()
+        .flat_map(|artist| split_artists(artist))
+        .map(|name| {
+            name.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+        })
+        .filter(|name| !name.is_empty())
+        .map(|name| format!("#{}", name))
+        .collect()
+}
+
+/// Returns the hashtags of the weekday of `date`, or none if the weekday has no hashtags
+///
+/// # Arguments
+///
+/// * `weekday_hashtags` - The mapping of weekdays to hashtags
+/// * `date` - The date whose weekday is looked up
+pub fn weekday_hashtags(
+    weekday_hashtags: &HashMap<Weekday, Vec<String>>,
+    date: NaiveDate,
+) -> Vec<String> {
+    weekday_hashtags
+        .get(&date.weekday())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// The parts that are appended to the card text of a post
+#[derive(Clone, Debug, Default)]
+pub struct PostOptions {
+    /// A price line as returned by [`get_price`]
+    pub price: Option<String>,
+    /// Notes as returned by [`get_notes`], added after the price
+    pub notes: Vec<String>,
+    /// Links added after the artist, each on its own line
+    pub links: Vec<String>,
+    /// Hashtags added after the links
+    pub hashtags: Option<String>,
+    /// A line as returned by [`get_oracle_id`], added at the very end of the post
+    pub oracle_id: Option<String>,
+    /// A header as returned by [`post_header`], added before the text of the first post only
+    pub header: Option<String>,
+}
+
+/// How many characters a platform counts for a link
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkCost {
+    /// Every link counts as this many characters, regardless of its length
+    Fixed(usize),
+    /// Links count with their actual length
+    Literal,
+}
+
+impl PostOptions {
+    /// Returns how many characters [`compose_post`] adds to a card text
+    ///
+    /// # Arguments
+    ///
+    /// * `artist` - The artist as returned by [`get_artist`]
+    /// * `link_cost` - How the platform counts links
+    pub fn footer_length(&self, artist: &Option<String>, link_cost: LinkCost) -> usize {
+        let without_links = PostOptions {
+            links: vec![],
+            ..self.clone()
+        };
+        let length = compose_post("", artist, &without_links).chars().count();
+        if self.links.is_empty() {
+            return length;
+        }
+
+        let links_length: usize = self
+            .links
+            .iter()
+            .map(|link| match link_cost {
+                LinkCost::Fixed(cost) => cost,
+                LinkCost::Literal => link.chars().count(),
+            })
+            .sum();
+        // the blank line before the links and the line breaks between them
+        let separators_length = 2 + self.links.len() - 1;
+        return length + links_length + separators_length;
+    }
+}
+
+/// Composes a post from a card text, the artist and the given [`PostOptions`]
+///
+/// The parts are appended in the order `{text}{price}{notes}{artist}{links}{hashtags}{oracle_id}`.
+///
+/// # Arguments
+///
+/// * `text` - A card text as returned by [`format_card`]
+/// * `artist` - The artist as returned by [`get_artist`]
+/// * `options` - The [`PostOptions`] to append
+pub fn compose_post(text: &str, artist: &Option<String>, options: &PostOptions) -> String {
+    let mut builder = Builder::default();
+    builder.append(text);
+    let info = options
+        .price
+        .iter()
+        .chain(options.notes.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+    if !info.is_empty() {
+        builder.append(format!("\n\n{}", info.join("\n")));
+    }
+    builder.append(artist.clone().unwrap_or_default());
+    if !options.links.is_empty() {
+        builder.append(format!("\n\n{}", options.links.join("\n")));
+    }
+    if let Some(hashtags) = &options.hashtags {
+        builder.append(format!("\n{}", hashtags));
+    }
+    if let Some(oracle_id) = &options.oracle_id {
+        builder.append(format!("\n{}", oracle_id));
+    }
+    return builder.string().unwrap();
+}
+
+/// How much text a platform allows, every backend composes its posts with its own budget
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PostBudget {
+    /// The maximum number of characters of a single post
+    pub character_limit: usize,
+    /// How the platform counts links
+    pub link_cost: LinkCost,
+    /// The maximum number of posts of a thread, see [`crate::util::limit_posts`]
+    pub max_posts: Option<usize>,
+    /// Whether the posts of a thread are prefixed with their position like `(1/3) `
+    pub numbering: bool,
+}
+
+/// Splits a text into posts that fit into the [`PostBudget`] and composes each of them
+///
+/// The characters [`compose_post`] appends are reserved in every post, as is the position of the
+/// post if the thread is numbered.
+///
+/// # Arguments
+///
+/// * `text` - A card text as returned by [`format_card`]
+/// * `artist` - The artist as returned by [`get_artist`]
+/// * `options` - The [`PostOptions`] to append to every post
+/// * `budget` - The [`PostBudget`] of the platform
+pub fn compose_thread(
+    text: &str,
+    artist: &Option<String>,
+    options: &PostOptions,
+    budget: PostBudget,
+) -> Vec<String> {
+    let text = &with_header(text, &options.header);
+    let footer_length = options.footer_length(artist, budget.link_cost);
+    let mut splitted_texts = split_thread(text, footer_length, budget);
+
+    if budget.numbering && splitted_texts.len() > 1 {
+        // the positions get longer with the number of posts, so split again until they fit
+        let mut prefix_length = 0;
+        loop {
+            let count = splitted_texts.len();
+            let needed_length = thread_position(count, count).chars().count();
+            if needed_length <= prefix_length {
+                break;
+            }
+            prefix_length = needed_length;
+            splitted_texts = split_thread(text, footer_length + prefix_length, budget);
+        }
+        let count = splitted_texts.len();
+        splitted_texts = splitted_texts
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| format!("{}{}", thread_position(index + 1, count), text))
+            .collect();
+    }
+
+    splitted_texts
+        .iter()
+        .map(|text| compose_post(text, artist, options))
+        .collect()
+}
+
+fn split_thread(text: &str, reserved: usize, budget: PostBudget) -> Vec<String> {
+    limit_posts(
+        split_text(
+            text.to_owned(),
+            budget.character_limit,
+            vec![Additional::Number(reserved)],
+        ),
+        budget.max_posts,
+    )
+}
+
+/// Puts the header in front of the text, it counts towards the first post like the text itself
+pub fn with_header(text: &str, header: &Option<String>) -> String {
+    match header {
+        Some(header) => format!("{}\n\n{}", header, text),
+        None => text.to_owned(),
+    }
+}
+
+/// Renders `DAILY_SCRY_POST_HEADER`, replacing `{date}` with `date` like `2024-04-02`
+///
+/// # Arguments
+///
+/// * `template` - The header template
+/// * `date` - Today's date in `DAILY_SCRY_TIMEZONE`
+pub fn post_header(template: &str, date: NaiveDate) -> String {
+    template.replace("{date}", &date.format("%Y-%m-%d").to_string())
+}
+
+/// The prefix of a numbered post, e.g. `(1/3) `
+fn thread_position(position: usize, count: usize) -> String {
+    format!("({}/{}) ", position, count)
+}
+
+/// Faces whose formatted text is shorter than this are considered to carry no information
+const MIN_FACE_TEXT_LENGTH: usize = 20;
+
+/// Blanks out faces that carry no meaningful text, like the "Angel\nToken" back of a double-faced
+/// token
+///
+/// The faces are replaced by empty strings instead of being removed so that they still line up
+/// with the downloaded images. If every face would be blanked the texts are returned unchanged.
+///
+/// # Arguments
+///
+/// * `card_texts` - The texts as returned by [`format_card`]
+pub fn blank_empty_faces(card_texts: Vec<String>) -> Vec<String> {
+    let is_empty = |text: &String| text.trim().chars().count() < MIN_FACE_TEXT_LENGTH;
+    if card_texts.iter().all(is_empty) {
+        return card_texts;
+    }
+    return card_texts
+        .into_iter()
+        .map(|text| if is_empty(&text) { String::new() } else { text })
+        .collect();
+}
+
+/// Joins the texts of all faces into the first one, separated by `face_separator`
+///
+/// The other faces are replaced by empty strings so that they still line up with the downloaded
+/// images. Faces that are already empty are skipped.
+///
+/// # Arguments
+///
+/// * `card_texts` - The texts as returned by [`format_card`]
+/// * `face_separator` - The separator between two faces
+pub fn combine_face_texts(card_texts: Vec<String>, face_separator: &str) -> Vec<String> {
+    let combined = card_texts
+        .iter()
+        .filter(|text| !text.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(face_separator);
+    let mut result = vec![String::new(); card_texts.len().max(1)];
+    result[0] = combined;
+    return result;
+}
+
+fn format_normal_layout(card: &Card, format_options: &FormatOptions) -> Result<Vec<String>> {
     let mut builder = Builder::default();
 
-    let type_line = card.type_line.clone().unwrap();
+    let type_line = card.type_line.clone().ok_or(Error::TextNotFound)?;
+
+    rarity_emoji(&mut builder, card, format_options);
 
     if type_line.contains("Creature") {
-        format_creature(&mut builder, &CardOrFace::Card(card));
-        artist(&mut builder, &CardOrFace::Card(card));
+        format_creature(&mut builder, &CardOrFace::Card(card), format_options);
+        artist(&mut builder, &CardOrFace::Card(card), format_options);
         return builder
             .string()
             .map(|str| vec![str])
@@ -101,8 +663,8 @@ fn format_normal_layout(card: &Card) -> Result<Vec<String>> {
     }
 
     if type_line.contains("Planeswalker") {
-        format_planeswalker(&mut builder, &CardOrFace::Card(card));
-        artist(&mut builder, &CardOrFace::Card(card));
+        format_planeswalker(&mut builder, &CardOrFace::Card(card), format_options);
+        artist(&mut builder, &CardOrFace::Card(card), format_options);
         return builder
             .string()
             .map(|str| vec![str])
@@ -110,7 +672,7 @@ fn format_normal_layout(card: &Card) -> Result<Vec<String>> {
     }
 
     if type_line.contains("Vanguard") {
-        format_vanguard(&mut builder, &CardOrFace::Card(card));
+        format_vanguard(&mut builder, &CardOrFace::Card(card), format_options);
     }
 
     if type_line.contains("Instant")
@@ -124,35 +686,44 @@ fn format_normal_layout(card: &Card) -> Result<Vec<String>> {
         || type_line.contains("Emblem")
         || type_line.contains("Battle")
     {
-        format_non_creature(&mut builder, &CardOrFace::Card(card));
+        format_non_creature(&mut builder, &CardOrFace::Card(card), format_options);
     }
 
     if type_line == "Token" {
-        format_token(&mut builder, &CardOrFace::Card(card));
+        format_token(&mut builder, &CardOrFace::Card(card), format_options);
     }
 
-    artist(&mut builder, &CardOrFace::Card(card));
+    artist(&mut builder, &CardOrFace::Card(card), format_options);
     return builder
         .string()
         .map(|str| vec![str])
         .map_err(|_| Error::TextNotFound);
 }
 
-fn format_multiple_faces_layout(card: &Card) -> Result<Vec<String>> {
-    let faces = card.card_faces.clone().unwrap();
-    faces
+fn format_multiple_faces_layout(
+    card: &Card,
+    format_options: &FormatOptions,
+) -> Result<Vec<String>> {
+    let faces = card.card_faces.clone().ok_or(Error::TextNotFound)?;
+    let face_count = faces
+        .len()
+        .min(format_options.max_faces.unwrap_or(usize::MAX));
+    let mut texts = faces
         .iter()
+        .take(face_count)
         .map(|face| {
             let mut builder = Builder::default();
-            let type_line = face.type_line.clone().unwrap();
+            let type_line = face.type_line.clone().ok_or(Error::TextNotFound)?;
+
+            rarity_emoji(&mut builder, card, format_options);
 
             if type_line.contains("Creature") {
-                format_creature(&mut builder, &CardOrFace::Face(&face));
+                format_creature(&mut builder, &CardOrFace::Face(&face), format_options);
                 return builder.string().map_err(|_| Error::TextNotFound);
             }
 
             if type_line.contains("Planeswalker") {
-                format_planeswalker(&mut builder, &CardOrFace::Face(&face));
+                format_planeswalker(&mut builder, &CardOrFace::Face(&face), format_options);
             }
 
             if type_line.contains("Instant")
@@ -163,73 +734,212 @@ fn format_multiple_faces_layout(card: &Card) -> Result<Vec<String>> {
                 || type_line.contains("Emblem")
                 || type_line.contains("Battle")
             {
-                format_non_creature(&mut builder, &CardOrFace::Face(&face));
+                format_non_creature(&mut builder, &CardOrFace::Face(&face), format_options);
             }
 
             if type_line == "Token" {
-                format_token(&mut builder, &CardOrFace::Face(&face));
+                format_token(&mut builder, &CardOrFace::Face(&face), format_options);
             }
 
             if type_line == "Card" {
-                format_art_card(&mut builder, &CardOrFace::Face(&face));
+                format_art_card(&mut builder, &CardOrFace::Face(&face), format_options);
             }
 
             return builder.string().map_err(|_| Error::TextNotFound);
         })
-        .collect()
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(last_text) = texts.last_mut().filter(|_| face_count < faces.len()) {
+        last_text.push_str(&truncated_faces_note(faces.len() - face_count));
+    }
+    Ok(texts)
 }
 
-fn format_single_image_multiple_faces_layout(card: &Card) -> Result<Vec<String>> {
-    let faces = format_multiple_faces_layout(card)?;
+/// The note appended to the last face if `DAILY_SCRY_MAX_FACES` left out the rest
+fn truncated_faces_note(count: usize) -> String {
+    match count {
+        1 => "\n\n(+1 more face)".to_owned(),
+        count => format!("\n\n(+{} more faces)", count),
+    }
+}
+
+fn format_single_image_multiple_faces_layout(
+    card: &Card,
+    format_options: &FormatOptions,
+) -> Result<Vec<String>> {
+    let faces = format_multiple_faces_layout(card, format_options)?;
     let mut builder = Builder::default();
 
-    builder.append(format!("{}", faces.join("\n\n")));
+    builder.append(format!(
+        "{}",
+        collapse_blank_lines(faces).join(&format_options.face_separator)
+    ));
 
-    artist(&mut builder, &CardOrFace::Card(&card));
+    artist(&mut builder, &CardOrFace::Card(&card), format_options);
     return builder
         .string()
         .map(|str| vec![str])
         .map_err(|_| Error::TextNotFound);
 }
 
-fn format_creature(builder: &mut Builder, card_or_face: &CardOrFace) {
+/// Returns a best-effort string representation of a [`scryfall::card::Card`] whose layout is not
+/// supported by [`format_card`]
+///
+/// Only the name, mana cost, type line, oracle text, power and toughness or loyalty and the artist
+/// are rendered.
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+/// * `format_options` - The [`FormatOptions`] to use
+pub fn format_generic(card: &Card, format_options: &FormatOptions) -> Result<Vec<String>> {
+    trace!("formatting card generically…");
+    let mut builder = Builder::default();
+
+    match card.card_faces.clone() {
+        Some(faces) => {
+            for (index, face) in faces.iter().enumerate() {
+                if index > 0 {
+                    builder.append("\n\n");
+                }
+                rarity_emoji(&mut builder, card, format_options);
+                format_generic_card_or_face(&mut builder, &CardOrFace::Face(face), format_options);
+            }
+        }
+        None => {
+            rarity_emoji(&mut builder, card, format_options);
+            format_generic_card_or_face(&mut builder, &CardOrFace::Card(card), format_options);
+        }
+    }
+
+    artist(&mut builder, &CardOrFace::Card(card), format_options);
+    return builder
+        .string()
+        .map(|str| collapse_blank_lines(vec![str]))
+        .map_err(|_| Error::TextNotFound);
+}
+
+/// Collapses three or more consecutive newlines in each text down to two
+///
+/// Depending on which optional sections of a card are present, the sections can add up to more
+/// than one blank line in a row.
+fn collapse_blank_lines(card_texts: Vec<String>) -> Vec<String> {
+    card_texts
+        .into_iter()
+        .map(|text| {
+            let mut result = String::with_capacity(text.len());
+            let mut newlines = 0;
+            for character in text.chars() {
+                if character == '\n' {
+                    newlines += 1;
+                    if newlines > 2 {
+                        continue;
+                    }
+                } else {
+                    newlines = 0;
+                }
+                result.push(character);
+            }
+            result
+        })
+        .collect()
+}
+
+fn format_generic_card_or_face(
+    builder: &mut Builder,
+    card_or_face: &CardOrFace,
+    format_options: &FormatOptions,
+) {
+    name_and_mana_cost(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
+    oracle_text(builder, card_or_face);
+    if has_power_and_toughness(card_or_face) {
+        power_and_toughness(builder, card_or_face);
+    }
+    loyalty(builder, card_or_face);
+}
+
+fn format_creature(
+    builder: &mut Builder,
+    card_or_face: &CardOrFace,
+    format_options: &FormatOptions,
+) {
     name_and_mana_cost(builder, card_or_face);
-    type_line(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
     oracle_text(builder, card_or_face);
     flavour_text(builder, card_or_face);
     power_and_toughness(builder, card_or_face);
 }
 
-fn format_non_creature(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn format_non_creature(
+    builder: &mut Builder,
+    card_or_face: &CardOrFace,
+    format_options: &FormatOptions,
+) {
     name_and_mana_cost(builder, card_or_face);
-    type_line(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
     oracle_text(builder, card_or_face);
     flavour_text(builder, card_or_face);
 }
 
-fn format_planeswalker(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn format_planeswalker(
+    builder: &mut Builder,
+    card_or_face: &CardOrFace,
+    format_options: &FormatOptions,
+) {
     name_and_mana_cost(builder, card_or_face);
-    type_line(builder, card_or_face);
-    oracle_text(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
+    loyalty_abilities(builder, card_or_face);
     loyalty(builder, card_or_face);
 }
 
-fn format_token(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn format_token(builder: &mut Builder, card_or_face: &CardOrFace, format_options: &FormatOptions) {
     name_and_mana_cost(builder, card_or_face);
-    type_line(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
 }
 
-fn format_vanguard(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn format_vanguard(
+    builder: &mut Builder,
+    card_or_face: &CardOrFace,
+    format_options: &FormatOptions,
+) {
     name_and_mana_cost(builder, card_or_face);
-    type_line(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
     oracle_text(builder, card_or_face);
     vanguard_stats(builder, card_or_face);
     flavour_text(builder, card_or_face);
 }
 
-fn format_art_card(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn format_art_card(
+    builder: &mut Builder,
+    card_or_face: &CardOrFace,
+    format_options: &FormatOptions,
+) {
     name_and_mana_cost(builder, card_or_face);
-    type_line(builder, card_or_face);
+    type_line(builder, card_or_face, format_options);
+}
+
+/// Returns the emoji for the rarity of a card, special and bonus rarities have none
+fn emoji_for_rarity(rarity: Rarity) -> Option<&'static str> {
+    match rarity {
+        Rarity::Common => Some("⚪"),
+        Rarity::Uncommon => Some("🔵"),
+        Rarity::Rare => Some("🟡"),
+        Rarity::Mythic => Some("🟠"),
+        _ => None,
+    }
+}
+
+/// Prefixes the name line that follows with the emoji for the rarity of `card`
+///
+/// Faces don't have a rarity, so the rarity of the card is used for all of them.
+fn rarity_emoji(builder: &mut Builder, card: &Card, format_options: &FormatOptions) {
+    if !format_options.rarity_emoji {
+        return;
+    }
+    if let Some(emoji) = emoji_for_rarity(card.rarity) {
+        builder.append(format!("{} ", emoji));
+    }
 }
 
 fn name_and_mana_cost(builder: &mut Builder, card_or_face: &CardOrFace) {
@@ -254,34 +964,82 @@ fn name_and_mana_cost(builder: &mut Builder, card_or_face: &CardOrFace) {
     }
 }
 
-fn type_line(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn type_line(builder: &mut Builder, card_or_face: &CardOrFace, format_options: &FormatOptions) {
     let type_line: String;
     match card_or_face {
         &CardOrFace::Card(card) => {
-            type_line = card.type_line.clone().unwrap_or_default();
+            type_line = card
+                .printed_type_line
+                .clone()
+                .filter(|_| format_options.printed_type_line)
+                .or(card.type_line.clone())
+                .unwrap_or_default();
         }
         &CardOrFace::Face(face) => {
-            type_line = face.type_line.clone().unwrap_or_default();
+            type_line = face
+                .printed_type_line
+                .clone()
+                .filter(|_| format_options.printed_type_line)
+                .or(face.type_line.clone())
+                .unwrap_or_default();
         }
     }
     builder.append(format!("\n{}", type_line));
 }
 
 fn oracle_text(builder: &mut Builder, card_or_face: &CardOrFace) {
-    let oracle_text: String;
-    match card_or_face {
-        &CardOrFace::Card(card) => {
-            oracle_text = card.oracle_text.clone().unwrap_or_default();
-        }
-        &CardOrFace::Face(face) => {
-            oracle_text = face.oracle_text.clone().unwrap_or_default();
-        }
+    let oracle_text = get_oracle_text(card_or_face);
+    if !oracle_text.is_empty() {
+        builder.append(format!("\n{}", oracle_text));
     }
+}
+
+/// Appends the oracle text of a planeswalker with every loyalty ability on its own line
+fn loyalty_abilities(builder: &mut Builder, card_or_face: &CardOrFace) {
+    let oracle_text = separate_loyalty_abilities(&get_oracle_text(card_or_face));
     if !oracle_text.is_empty() {
         builder.append(format!("\n{}", oracle_text));
     }
 }
 
+fn get_oracle_text(card_or_face: &CardOrFace) -> String {
+    match card_or_face {
+        &CardOrFace::Card(card) => card.oracle_text.clone().unwrap_or_default(),
+        &CardOrFace::Face(face) => face.oracle_text.clone().unwrap_or_default(),
+    }
+}
+
+/// Starts a new line before every loyalty ability that shares a line with the previous text
+///
+/// Scryfall already puts every ability on its own line, so this only changes texts where they
+/// were joined, e.g. `+1: You gain 2 life. −1: Put …`.
+fn separate_loyalty_abilities(oracle_text: &str) -> String {
+    let mut separated = String::new();
+    for (index, word) in oracle_text.split(' ').enumerate() {
+        if index > 0 {
+            separated.push(if is_loyalty_cost(word) { '\n' } else { ' ' });
+        }
+        separated.push_str(word);
+    }
+    separated
+}
+
+/// Whether `word` is the cost of a loyalty ability like `+1:`, `−X:` or `0:`
+fn is_loyalty_cost(word: &str) -> bool {
+    let Some(cost) = word.strip_suffix(':') else {
+        return false;
+    };
+    if cost == "0" {
+        return true;
+    }
+    match cost.strip_prefix(['+', '−', '-']) {
+        Some(amount) => {
+            amount == "X" || (!amount.is_empty() && amount.chars().all(|c| c.is_ascii_digit()))
+        }
+        None => false,
+    }
+}
+
 fn flavour_text(builder: &mut Builder, card_or_face: &CardOrFace) {
     let flavour_text: Option<String>;
     match card_or_face {
@@ -313,7 +1071,14 @@ fn power_and_toughness(builder: &mut Builder, card_or_face: &CardOrFace) {
     builder.append(format!("\n\n{}/{}", power, toughness));
 }
 
-fn artist(builder: &mut Builder, card_or_face: &CardOrFace) {
+fn has_power_and_toughness(card_or_face: &CardOrFace) -> bool {
+    match card_or_face {
+        &CardOrFace::Card(card) => card.power.is_some() && card.toughness.is_some(),
+        &CardOrFace::Face(face) => face.power.is_some() && face.toughness.is_some(),
+    }
+}
+
+fn artist(builder: &mut Builder, card_or_face: &CardOrFace, format_options: &FormatOptions) {
     let artist: Option<String>;
     match card_or_face {
         &CardOrFace::Card(card) => {
@@ -323,65 +1088,632 @@ fn artist(builder: &mut Builder, card_or_face: &CardOrFace) {
             artist = face.artist.clone();
         }
     }
-    if artist.is_some() {
-        builder.append(format!("\n\nIllustrated by {}", artist.unwrap()));
+    if let Some(artist) = artist {
+        let artist = if format_options.join_artists_with_and {
+            join_with_and(&split_artists(&artist))
+        } else {
+            artist
+        };
+        builder.append(format!(
+            "\n\n{}",
+            format_options.artist_template.replace("{artist}", &artist)
+        ));
+    }
+}
+
+/// Splits the artist of collaborative art, which scryfall joins with `&`
+pub fn split_artists(artist: &str) -> Vec<&str> {
+    artist
+        .split('&')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Joins names like `A, B and C`
+fn join_with_and(names: &[&str]) -> String {
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+fn loyalty(builder: &mut Builder, card_or_face: &CardOrFace) {
+    let loyalty: Option<String>;
+    match card_or_face {
+        &CardOrFace::Card(card) => {
+            loyalty = card.loyalty.clone();
+        }
+        &CardOrFace::Face(face) => {
+            loyalty = face.loyalty.clone();
+        }
+    }
+    if loyalty.is_some() {
+        builder.append(format!("\nLoyalty: {}", loyalty.unwrap()));
+    }
+}
+
+fn vanguard_stats(builder: &mut Builder, card_or_face: &CardOrFace) {
+    let hand_modifier: Option<String>;
+    let life_modifier: Option<String>;
+    match card_or_face {
+        &CardOrFace::Card(card) => {
+            hand_modifier = card.hand_modifier.clone();
+            life_modifier = card.life_modifier.clone();
+        }
+        &CardOrFace::Face(_) => {
+            // There are no multifaced vanguard cards
+            return;
+        }
+    }
+    if hand_modifier.is_some() && life_modifier.is_some() {
+        builder.append(format!(
+            "\n\nHand Size: {}\nStarting Life: {}",
+            hand_modifier.unwrap(),
+            life_modifier.unwrap()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::with_printed_text;
+    use crate::fixtures::card_fixture;
+
+    #[test]
+    fn test_format_card_grizzly_bears() {
+        let expected_string = "Grizzly Bears\t{1}{G}\n\
+        Creature — Bear\n\
+        \n\
+        Don't try to outrun one of Dominia's Grizzlies; it'll catch you, knock you down, and eat you. Of course, you could run up a tree. In that case you'll get a nice view before it knocks the tree down and eats you.\n\
+        \n\
+        2/2\n\
+        \n\
+        Illustrated by Jeff A. Menges".to_owned();
+        let grizzly_bears = card_fixture("grizzly_bears");
+        assert_eq!(
+            format_card(&grizzly_bears, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&grizzly_bears, &FormatOptions::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_card_collapses_blank_lines() {
+        let expected_string = "Grizzly Bears\t{1}{G}\n\
+        Creature — Bear\n\
+        \n\
+        2/2\n\
+        \n\
+        Illustrated by Jeff A. Menges"
+            .to_owned();
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.flavor_text = Some("".to_owned());
+        assert_eq!(
+            format_card(&grizzly_bears, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+    }
+
+    #[test]
+    fn test_collapse_blank_lines() {
+        assert_eq!(
+            collapse_blank_lines(vec![
+                "a\n\n\nb\n\n\n\nc".to_owned(),
+                "a\n\nb\nc".to_owned(),
+                "".to_owned()
+            ]),
+            vec!["a\n\nb\n\nc", "a\n\nb\nc", ""]
+        );
+    }
+
+    #[test]
+    fn test_format_generic_grizzly_bears() {
+        let expected_string = "Grizzly Bears\t{1}{G}\n\
+        Creature — Bear\n\
+        \n\
+        2/2\n\
+        \n\
+        Illustrated by Jeff A. Menges"
+            .to_owned();
+        let grizzly_bears = card_fixture("grizzly_bears");
+        assert_eq!(
+            format_generic(&grizzly_bears, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_generic_unknown_layout() {
+        let expected_string = "Fireball\t{X}{R}\n\
+        Sorcery\n\
+        This spell costs {1} more to cast for each target beyond the first.\nFireball deals X damage divided evenly, rounded down, among any number of targets.\n\
+        \n\
+        Illustrated by Mark Tedin".to_owned();
+        let mut fireball = Card::multiverse(197).await.unwrap();
+        fireball.layout = Layout::Battle;
+        assert!(matches!(
+            format_card(&fireball, &FormatOptions::default()),
+            Err(Error::UnknownCardLayout { .. })
+        ));
+        assert_eq!(
+            format_generic(&fireball, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_notes_watermark_and_story_spotlight() {
+        let mut grizzly_bears = Card::multiverse(155).await.unwrap();
+        assert_eq!(get_notes(&grizzly_bears, true, true), Vec::<String>::new());
+
+        grizzly_bears.watermark = Some("selesnya".to_owned());
+        grizzly_bears.story_spotlight = true;
+        assert_eq!(
+            get_notes(&grizzly_bears, true, true),
+            vec!["★ Story Spotlight", "Watermark: Selesnya"]
+        );
+        assert_eq!(
+            get_notes(&grizzly_bears, false, true),
+            vec!["Watermark: Selesnya"]
+        );
+        assert_eq!(
+            get_notes(&grizzly_bears, false, false),
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_edhrec_rank() {
+        let mut grizzly_bears = Card::multiverse(155).await.unwrap();
+        grizzly_bears.edhrec_rank = Some(1234);
+        assert_eq!(
+            get_edhrec_rank(&grizzly_bears).as_deref(),
+            Some("EDHREC rank: 1234")
+        );
+
+        grizzly_bears.edhrec_rank = None;
+        assert_eq!(get_edhrec_rank(&grizzly_bears), None);
+    }
+
+    #[test]
+    fn test_compose_post_with_notes() {
+        let options = PostOptions {
+            price: Some("~$3.50 (USD)".to_owned()),
+            notes: vec!["Watermark: Selesnya".to_owned()],
+            ..Default::default()
+        };
+        let artist = Some("\n\nIllustrated by Jeff A. Menges".to_owned());
+        assert_eq!(
+            compose_post("Grizzly Bears", &artist, &options),
+            "Grizzly Bears\n\n~$3.50 (USD)\nWatermark: Selesnya\n\nIllustrated by Jeff A. Menges"
+        );
+        assert_eq!(
+            options.footer_length(&artist, LinkCost::Literal),
+            compose_post("", &artist, &options).chars().count()
+        );
+    }
+
+    #[test]
+    fn test_get_provenance_grizzly_bears() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        assert_eq!(
+            get_provenance(&grizzly_bears, false),
+            format!(
+                "from Limited Edition Alpha #{}",
+                grizzly_bears.collector_number
+            )
+        );
+        assert_eq!(
+            get_provenance(&grizzly_bears, true),
+            get_provenance(&grizzly_bears, false)
+        );
+    }
+
+    #[test]
+    fn test_get_provenance_printed_type_line() {
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.printed_type_line = Some("Kreatur — Bär".to_owned());
+        assert_eq!(
+            get_provenance(&grizzly_bears, true),
+            format!(
+                "from Limited Edition Alpha #{} (Kreatur — Bär)",
+                grizzly_bears.collector_number
+            )
+        );
+        assert!(!get_provenance(&grizzly_bears, false).contains("Kreatur"));
+    }
+
+    #[test]
+    fn test_two_artists() {
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.artist = Some("Jesper Ejsing & Sam Wood-Burke".to_owned());
+
+        let card_text = format_card(&grizzly_bears, &FormatOptions::default()).unwrap()[0].clone();
+        assert!(card_text.ends_with("Illustrated by Jesper Ejsing & Sam Wood-Burke"));
+
+        let format_options = FormatOptions {
+            join_artists_with_and: true,
+            ..Default::default()
+        };
+        let card_text = format_card(&grizzly_bears, &format_options).unwrap()[0].clone();
+        assert!(card_text.ends_with("Illustrated by Jesper Ejsing and Sam Wood-Burke"));
+
+        assert_eq!(
+            artist_hashtags(&grizzly_bears),
+            vec!["#JesperEjsing", "#SamWoodBurke"]
+        );
+    }
+
+    #[test]
+    fn test_join_with_and() {
+        assert_eq!(join_with_and(&[]), "");
+        assert_eq!(join_with_and(&["A"]), "A");
+        assert_eq!(join_with_and(&["A", "B"]), "A and B");
+        assert_eq!(join_with_and(&["A", "B", "C"]), "A, B and C");
+    }
+
+    #[test]
+    fn test_year_hashtag_grizzly_bears() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        assert_eq!(year_hashtag(&grizzly_bears), "#MTG1993");
+    }
+
+    #[test]
+    fn test_ability_word() {
+        assert_eq!(
+            ability_word("Landfall — Whenever a land you control enters, Steppe Lynx gets +2/+2 until end of turn."),
+            Some("Landfall")
+        );
+        assert_eq!(
+            ability_word("Fateful hour — As long as you have 5 or less life, creatures you control get +2/+2."),
+            Some("Fateful hour")
+        );
+        assert_eq!(ability_word("Flying — Whatever"), None);
+        assert_eq!(ability_word("Landfall, and more"), None);
+        assert_eq!(ability_word("Whenever Landfall — Whatever"), None);
+    }
+
+    #[test]
+    fn test_emoji_for_rarity() {
+        assert_eq!(emoji_for_rarity(Rarity::Common), Some("⚪"));
+        assert_eq!(emoji_for_rarity(Rarity::Uncommon), Some("🔵"));
+        assert_eq!(emoji_for_rarity(Rarity::Rare), Some("🟡"));
+        assert_eq!(emoji_for_rarity(Rarity::Mythic), Some("🟠"));
+        assert_eq!(emoji_for_rarity(Rarity::Special), None);
+        assert_eq!(emoji_for_rarity(Rarity::Bonus), None);
+    }
+
+    #[tokio::test]
+    async fn test_format_card_rarity_emoji() {
+        let options = FormatOptions {
+            rarity_emoji: true,
+            ..Default::default()
+        };
+        let mut grizzly_bears = Card::multiverse(155).await.unwrap();
+        for (rarity, emoji) in [
+            (Rarity::Common, "⚪"),
+            (Rarity::Uncommon, "🔵"),
+            (Rarity::Rare, "🟡"),
+            (Rarity::Mythic, "🟠"),
+        ] {
+            grizzly_bears.rarity = rarity;
+            let result = format_card(&grizzly_bears, &options).unwrap();
+            assert!(result[0].starts_with(&format!("{} Grizzly Bears\t{{1}}{{G}}", emoji)));
+        }
+
+        grizzly_bears.rarity = Rarity::Special;
+        let result = format_card(&grizzly_bears, &options).unwrap();
+        assert!(result[0].starts_with("Grizzly Bears"));
+    }
+
+    #[test]
+    fn test_weekday_hashtags() {
+        let hashtags = HashMap::from([(Weekday::Thu, vec!["#ThrowbackThursday".to_owned()])]);
+        let thursday = NaiveDate::from_ymd_opt(2024, 4, 4).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 8).unwrap();
+        assert_eq!(
+            weekday_hashtags(&hashtags, thursday),
+            vec!["#ThrowbackThursday"]
+        );
+        assert_eq!(weekday_hashtags(&hashtags, monday), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_footer_length_weekday_hashtags() {
+        let hashtags = HashMap::from([(Weekday::Thu, vec!["#ThrowbackThursday".to_owned()])]);
+        let thursday = NaiveDate::from_ymd_opt(2024, 4, 4).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2024, 4, 8).unwrap();
+        let options_for = |date| PostOptions {
+            hashtags: Some(weekday_hashtags(&hashtags, date).join(" ")).filter(|h| !h.is_empty()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options_for(thursday).footer_length(&None, LinkCost::Literal),
+            "\n#ThrowbackThursday".chars().count()
+        );
+        assert_eq!(
+            options_for(monday).footer_length(&None, LinkCost::Literal),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_oracle_id() {
+        let mut swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+        let options = PostOptions {
+            oracle_id: get_oracle_id(&swamp),
+            ..Default::default()
+        };
+        assert_eq!(options.oracle_id, Some("oid: 56719f6a".to_owned()));
+
+        let posts = compose_thread(
+            &"a".repeat(40),
+            &None,
+            &options,
+            PostBudget {
+                character_limit: 30,
+                link_cost: LinkCost::Literal,
+                max_posts: None,
+                numbering: false,
+            },
+        );
+        assert!(posts.len() > 1);
+        assert!(posts
+            .iter()
+            .all(|post| post.chars().count() <= 30 && post.ends_with("\noid: 56719f6a")));
+
+        swamp.oracle_id = None;
+        assert_eq!(get_oracle_id(&swamp), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_price_black_lotus() {
+        let black_lotus = Card::multiverse(3).await.unwrap();
+        let usd_price = get_price(&black_lotus, "usd").unwrap();
+        assert!(usd_price.starts_with("~$"));
+        assert!(usd_price.ends_with(" (USD)"));
+        assert_eq!(None, get_price(&black_lotus, "tix"));
+    }
+
+    fn german_options() -> FormatOptions {
+        FormatOptions {
+            artist_template: "Illustriert von {artist}".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_card_custom_artist_template() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let result = format_card(&grizzly_bears, &german_options()).unwrap();
+        assert!(result[0].ends_with("\n\nIllustriert von Jeff A. Menges"));
+    }
+
+    #[test]
+    fn test_format_card_localized_creature() {
+        let expected_string = "Grizzlybären\t{1}{G}\n\
+        Kreatur — Bär\n\
+        \n\
+        2/2\n\
+        \n\
+        Illustriert von Jeff A. Menges"
+            .to_owned();
+        let mut grizzly_bears = card_fixture("grizzly_bears");
+        grizzly_bears.lang = "de".to_owned();
+        grizzly_bears.printed_name = Some("Grizzlybären".to_owned());
+        grizzly_bears.printed_type_line = Some("Kreatur — Bär".to_owned());
+        grizzly_bears.flavor_text = None;
+        let grizzly_bears = with_printed_text(&grizzly_bears);
+        let format_options = FormatOptions {
+            printed_type_line: true,
+            ..german_options()
+        };
+        assert_eq!(
+            format_card(&grizzly_bears, &format_options).unwrap()[0],
+            expected_string
+        );
+        assert!(format_card(&grizzly_bears, &german_options()).unwrap()[0]
+            .contains("\nCreature — Bear\n"));
+    }
+
+    #[tokio::test]
+    async fn test_get_artist_custom_artist_template() {
+        let kytheon = Card::multiverse(398428).await.unwrap();
+        assert_eq!(
+            Some("\n\nIllustriert von Willian Murai"),
+            get_artist(&kytheon, &german_options()).unwrap().as_deref()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_card_max_faces() {
+        let mut kytheon = Card::multiverse(398428).await.unwrap();
+        let mut faces = kytheon.card_faces.clone().unwrap();
+        faces.push(faces[0].clone());
+        kytheon.card_faces = Some(faces);
+        let options = FormatOptions {
+            max_faces: Some(2),
+            ..Default::default()
+        };
+
+        let card_texts = format_card(&kytheon, &options).unwrap();
+
+        assert_eq!(card_texts.len(), 2);
+        assert!(card_texts[0].starts_with("Kytheon, Hero of Akros"));
+        assert!(card_texts[1].ends_with("\n\n(+1 more face)"));
+        assert_eq!(
+            format_card(&kytheon, &FormatOptions::default())
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    fn ruling(published_at: &str) -> Ruling {
+        serde_json::from_value(serde_json::json!({
+            "object": "ruling",
+            "oracle_id": "56719f6a-1a6c-4c0a-8d21-18f7d7350b68",
+            "source": "wotc",
+            "published_at": published_at,
+            "comment": "A ruling.",
+        }))
+        .unwrap()
     }
-}
 
-fn loyalty(builder: &mut Builder, card_or_face: &CardOrFace) {
-    let loyalty: Option<String>;
-    match card_or_face {
-        &CardOrFace::Card(card) => {
-            loyalty = card.loyalty.clone();
-        }
-        &CardOrFace::Face(face) => {
-            loyalty = face.loyalty.clone();
-        }
+    #[test]
+    fn test_get_rulings_count() {
+        assert_eq!(get_rulings_count(&[]), None);
+        assert_eq!(
+            get_rulings_count(&[ruling("2004-10-04")]),
+            Some("1 official ruling, the latest from 2004-10-04".to_owned())
+        );
+        assert_eq!(
+            get_rulings_count(&[
+                ruling("2004-10-04"),
+                ruling("2024-04-02"),
+                ruling("2019-01-25")
+            ]),
+            Some("3 official rulings, the latest from 2024-04-02".to_owned())
+        );
     }
-    if loyalty.is_some() {
-        builder.append(format!("\nLoyalty: {}", loyalty.unwrap()));
+
+    #[test]
+    fn test_compose_thread_header() {
+        let options = PostOptions {
+            header: Some(post_header(
+                "MTG Card of the Day — {date}",
+                NaiveDate::from_ymd_opt(2024, 4, 2).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let budget = PostBudget {
+            character_limit: 50,
+            link_cost: LinkCost::Literal,
+            max_posts: None,
+            numbering: false,
+        };
+
+        let posts = compose_thread(&"a ".repeat(40), &None, &options, budget);
+
+        assert!(posts.len() > 1);
+        assert!(posts[0].starts_with("MTG Card of the Day — 2024-04-02\n\n"));
+        assert!(posts[1..]
+            .iter()
+            .all(|post| !post.contains("MTG Card of the Day")));
+        assert!(posts.iter().all(|post| post.chars().count() <= 50));
     }
-}
 
-fn vanguard_stats(builder: &mut Builder, card_or_face: &CardOrFace) {
-    let hand_modifier: Option<String>;
-    let life_modifier: Option<String>;
-    match card_or_face {
-        &CardOrFace::Card(card) => {
-            hand_modifier = card.hand_modifier.clone();
-            life_modifier = card.life_modifier.clone();
-        }
-        &CardOrFace::Face(_) => {
-            // There are no multifaced vanguard cards
-            return;
+    #[test]
+    fn test_compose_thread_numbering() {
+        let options = PostOptions {
+            hashtags: Some("#DailyScry".to_owned()),
+            ..Default::default()
+        };
+        let mut budget = PostBudget {
+            character_limit: 30,
+            link_cost: LinkCost::Literal,
+            max_posts: None,
+            numbering: true,
+        };
+
+        let posts = compose_thread(&"a".repeat(30), &None, &options, budget);
+
+        assert_eq!(posts.len(), 3);
+        for (index, post) in posts.iter().enumerate() {
+            assert!(post.starts_with(&format!("({}/3) a", index + 1)));
+            assert!(post.ends_with("\n#DailyScry"));
+            assert!(post.chars().count() <= 30);
         }
+
+        assert_eq!(
+            compose_thread("Grizzly Bears", &None, &options, budget),
+            vec!["Grizzly Bears\n#DailyScry"]
+        );
+
+        budget.numbering = false;
+        assert_eq!(
+            compose_thread(&"a".repeat(30), &None, &options, budget).len(),
+            2
+        );
     }
-    if hand_modifier.is_some() && life_modifier.is_some() {
-        builder.append(format!(
-            "\n\nHand Size: {}\nStarting Life: {}",
-            hand_modifier.unwrap(),
-            life_modifier.unwrap()
-        ));
+
+    #[test]
+    fn test_compose_post() {
+        let options = PostOptions {
+            price: Some("~$3.50 (USD)".to_owned()),
+            links: vec!["https://scryfall.com/card/lea/199".to_owned()],
+            hashtags: Some("#MagicTheGathering #DailyScry".to_owned()),
+            ..Default::default()
+        };
+        let artist = Some("\n\nIllustrated by Jeff A. Menges".to_owned());
+        assert_eq!(
+            compose_post("Grizzly Bears", &artist, &options),
+            "Grizzly Bears\n\n~$3.50 (USD)\n\nIllustrated by Jeff A. Menges\n\nhttps://scryfall.com/card/lea/199\n#MagicTheGathering #DailyScry"
+        );
+        assert_eq!(
+            compose_post("Grizzly Bears", &None, &PostOptions::default()),
+            "Grizzly Bears"
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_footer_length_no_links() {
+        let options = PostOptions {
+            hashtags: Some("#DailyScry".to_owned()),
+            ..Default::default()
+        };
+        let artist = Some("\n\nIllustrated by Jeff A. Menges".to_owned());
+        assert_eq!(
+            options.footer_length(&artist, LinkCost::Fixed(23)),
+            compose_post("", &artist, &options).chars().count()
+        );
+    }
 
-    #[tokio::test]
-    async fn test_format_card_grizzly_bears() {
-        let expected_string = "Grizzly Bears\t{1}{G}\n\
-        Creature — Bear\n\
-        \n\
-        Don't try to outrun one of Dominia's Grizzlies; it'll catch you, knock you down, and eat you. Of course, you could run up a tree. In that case you'll get a nice view before it knocks the tree down and eats you.\n\
-        \n\
-        2/2\n\
-        \n\
-        Illustrated by Jeff A. Menges".to_owned();
-        let grizzly_bears = Card::multiverse(155).await.unwrap();
-        assert_eq!(format_card(&grizzly_bears).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&grizzly_bears).unwrap());
+    #[test]
+    fn test_footer_length_one_link() {
+        let options = PostOptions {
+            links: vec!["https://scryfall.com/card/lea/199".to_owned()],
+            hashtags: Some("#DailyScry".to_owned()),
+            ..Default::default()
+        };
+        let literal_length = compose_post("", &None, &options).chars().count();
+        assert_eq!(
+            options.footer_length(&None, LinkCost::Literal),
+            literal_length
+        );
+        assert_eq!(
+            options.footer_length(&None, LinkCost::Fixed(23)),
+            literal_length - "https://scryfall.com/card/lea/199".len() + 23
+        );
+    }
+
+    #[test]
+    fn test_footer_length_multiple_links() {
+        let options = PostOptions {
+            links: vec![
+                "https://scryfall.com/card/lea/199".to_owned(),
+                "https://gatherer.wizards.com".to_owned(),
+                "https://edhrec.com".to_owned(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            options.footer_length(&None, LinkCost::Literal),
+            compose_post("", &None, &options).chars().count()
+        );
+        // "\n\n" + three links + two line breaks
+        assert_eq!(
+            options.footer_length(&None, LinkCost::Fixed(23)),
+            2 + 3 * 23 + 2
+        );
     }
 
     #[tokio::test]
@@ -396,8 +1728,14 @@ mod tests {
         \n\
         Illustrated by Christopher Rush".to_owned();
         let brainstorm = Card::multiverse(2497).await.unwrap();
-        assert_eq!(format_card(&brainstorm).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&brainstorm).unwrap());
+        assert_eq!(
+            format_card(&brainstorm, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&brainstorm, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -408,8 +1746,14 @@ mod tests {
         \n\
         Illustrated by Mark Tedin".to_owned();
         let fireball = Card::multiverse(197).await.unwrap();
-        assert_eq!(format_card(&fireball).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&fireball).unwrap());
+        assert_eq!(
+            format_card(&fireball, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&fireball, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -421,8 +1765,14 @@ mod tests {
         Illustrated by Christopher Rush"
             .to_owned();
         let black_lotus = Card::multiverse(3).await.unwrap();
-        assert_eq!(format_card(&black_lotus).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&black_lotus).unwrap());
+        assert_eq!(
+            format_card(&black_lotus, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&black_lotus, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -433,8 +1783,56 @@ mod tests {
         \n\
         Illustrated by Mark Poole".to_owned();
         let fastbond = Card::multiverse(148).await.unwrap();
-        assert_eq!(format_card(&fastbond).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&fastbond).unwrap());
+        assert_eq!(
+            format_card(&fastbond, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&fastbond, &FormatOptions::default()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_card_without_type_line() {
+        let mut grizzly_bears = Card::multiverse(155).await.unwrap();
+        grizzly_bears.type_line = None;
+
+        assert!(matches!(
+            format_card(&grizzly_bears, &FormatOptions::default()),
+            Err(Error::TextNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_format_card_without_faces() {
+        let mut grizzly_bears = Card::multiverse(155).await.unwrap();
+        grizzly_bears.layout = Layout::Transform;
+        grizzly_bears.card_faces = None;
+
+        assert!(matches!(
+            format_card(&grizzly_bears, &FormatOptions::default()),
+            Err(Error::TextNotFound)
+        ));
+        assert!(matches!(
+            get_artist(&grizzly_bears, &FormatOptions::default()),
+            Err(Error::TextNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_separate_loyalty_abilities() {
+        let oracle_text = "+1: You gain 2 life. −1: Put a +1/+1 counter on each creature you control. 0: Draw a card.\n−X: Destroy target creature with mana value X. −6: Create a white Avatar creature token.";
+        assert_eq!(
+            separate_loyalty_abilities(oracle_text),
+            "+1: You gain 2 life.\n−1: Put a +1/+1 counter on each creature you control.\n0: Draw a card.\n−X: Destroy target creature with mana value X.\n−6: Create a white Avatar creature token."
+        );
+    }
+
+    #[test]
+    fn test_separate_loyalty_abilities_keeps_separated_abilities() {
+        let oracle_text = "+1: You gain 2 life.\n−1: Put a +1/+1 counter on each creature you control. Those creatures gain vigilance until end of turn.\n−6: Create a white Avatar creature token. It has \"This creature's power and toughness are each equal to your life total.\"";
+        assert_eq!(separate_loyalty_abilities(oracle_text), oracle_text);
     }
 
     #[tokio::test]
@@ -446,8 +1844,11 @@ mod tests {
         \n\
         Illustrated by Aleksi Briclot".to_owned();
         let ajani = Card::multiverse(140233).await.unwrap();
-        assert_eq!(format_card(&ajani).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&ajani).unwrap());
+        assert_eq!(
+            format_card(&ajani, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(None, get_artist(&ajani, &FormatOptions::default()).unwrap());
     }
 
     #[tokio::test]
@@ -459,8 +1860,14 @@ mod tests {
         Illustrated by Rob Alexander"
             .to_owned();
         let badlands: Card = Card::multiverse(279).await.unwrap();
-        assert_eq!(format_card(&badlands).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&badlands).unwrap());
+        assert_eq!(
+            format_card(&badlands, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&badlands, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -476,8 +1883,14 @@ mod tests {
         Illustrated by David Martin"
             .to_owned();
         let stand_and_deliver: Card = Card::multiverse(20573).await.unwrap();
-        assert_eq!(format_card(&stand_and_deliver).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&stand_and_deliver).unwrap());
+        assert_eq!(
+            format_card(&stand_and_deliver, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&stand_and_deliver, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -495,8 +1908,50 @@ mod tests {
         Illustrated by Nils Hamm"
             .to_owned();
         let alive_and_well: Card = Card::multiverse(369041).await.unwrap();
-        assert_eq!(format_card(&alive_and_well).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&alive_and_well).unwrap());
+        assert_eq!(
+            format_card(&alive_and_well, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&alive_and_well, &FormatOptions::default()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_card_alive_and_well_custom_separator() {
+        let expected_string = "Alive\t{3}{G}\n\
+        Sorcery\n\
+        Create a 3/3 green Centaur creature token.\n\
+        Fuse (You may cast one or both halves of this card from your hand.)\n\
+        ———\n\
+        Well\t{W}\n\
+        Sorcery\n\
+        You gain 2 life for each creature you control.\n\
+        Fuse (You may cast one or both halves of this card from your hand.)\n\
+        \n\
+        Illustrated by Nils Hamm"
+            .to_owned();
+        let alive_and_well: Card = Card::multiverse(369041).await.unwrap();
+        let format_options = FormatOptions {
+            face_separator: "\n———\n".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_card(&alive_and_well, &format_options).unwrap()[0],
+            expected_string
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_card_alive_and_well_blank_line_separator() {
+        let alive_and_well: Card = Card::multiverse(369041).await.unwrap();
+        let format_options = FormatOptions {
+            face_separator: "\n\n\n———\n\n\n".to_owned(),
+            ..Default::default()
+        };
+        let text = &format_card(&alive_and_well, &format_options).unwrap()[0];
+        assert!(text.contains("from your hand.)\n\n\n———\n\n\nWell\t{W}"));
     }
 
     #[tokio::test]
@@ -513,10 +1968,13 @@ mod tests {
             .to_owned();
         let crime_and_punishment: Card = Card::multiverse(107285).await.unwrap();
         assert_eq!(
-            format_card(&crime_and_punishment).unwrap()[0],
+            format_card(&crime_and_punishment, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&crime_and_punishment).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&crime_and_punishment, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -533,10 +1991,13 @@ mod tests {
             .to_owned();
         let discovery_and_dispersal: Card = Card::multiverse(452973).await.unwrap();
         assert_eq!(
-            format_card(&discovery_and_dispersal).unwrap()[0],
+            format_card(&discovery_and_dispersal, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&discovery_and_dispersal).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&discovery_and_dispersal, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -554,8 +2015,14 @@ mod tests {
         Illustrated by Magali Villeneuve"
             .to_owned();
         let start_to_finish: Card = Card::multiverse(426917).await.unwrap();
-        assert_eq!(format_card(&start_to_finish).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&start_to_finish).unwrap());
+        assert_eq!(
+            format_card(&start_to_finish, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&start_to_finish, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -572,8 +2039,14 @@ mod tests {
         Illustrated by Daarken"
             .to_owned();
         let rever_to_return: Card = Card::multiverse(426914).await.unwrap();
-        assert_eq!(format_card(&rever_to_return).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&rever_to_return).unwrap());
+        assert_eq!(
+            format_card(&rever_to_return, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&rever_to_return, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -593,8 +2066,14 @@ mod tests {
         Illustrated by Mark Zug"
             .to_owned();
         let bushi_tenderfoot: Card = Card::multiverse(78600).await.unwrap();
-        assert_eq!(format_card(&bushi_tenderfoot).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&bushi_tenderfoot).unwrap());
+        assert_eq!(
+            format_card(&bushi_tenderfoot, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&bushi_tenderfoot, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -612,8 +2091,14 @@ mod tests {
         Illustrated by Randy Gallegos"
             .to_owned();
         let rune_tail: Card = Card::multiverse(87600).await.unwrap();
-        assert_eq!(format_card(&rune_tail).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&rune_tail).unwrap());
+        assert_eq!(
+            format_card(&rune_tail, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&rune_tail, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -632,12 +2117,14 @@ mod tests {
         0: Until end of turn, Gideon, Battle-Forged becomes a 4/4 Human Soldier creature with indestructible that's still a planeswalker. Prevent all damage that would be dealt to him this turn.\n\
         Loyalty: 3".to_owned();
         let kytheon: Card = Card::multiverse(398428).await.unwrap();
-        let result = format_card(&kytheon).unwrap();
+        let result = format_card(&kytheon, &FormatOptions::default()).unwrap();
         assert_eq!(result[0], face1);
         assert_eq!(result[1], face2);
         assert_eq!(
             Some("\n\nIllustrated by Willian Murai"),
-            get_artist(&kytheon).unwrap().as_deref()
+            get_artist(&kytheon, &FormatOptions::default())
+                .unwrap()
+                .as_deref()
         );
     }
 
@@ -659,12 +2146,14 @@ mod tests {
         —Kasla, Emeria shepherd"
             .to_owned();
         let emerias_call: Card = Card::multiverse(491633).await.unwrap();
-        let result = format_card(&emerias_call).unwrap();
+        let result = format_card(&emerias_call, &FormatOptions::default()).unwrap();
         assert_eq!(result[0], face1);
         assert_eq!(result[1], face2);
         assert_eq!(
             Some("\n\nIllustrated by Matt Stewart"),
-            get_artist(&emerias_call).unwrap().as_deref()
+            get_artist(&emerias_call, &FormatOptions::default())
+                .unwrap()
+                .as_deref()
         );
     }
 
@@ -681,8 +2170,14 @@ mod tests {
         \n\
         Illustrated by Clint Cearley".to_owned();
         let gisela: Card = Card::multiverse(414319).await.unwrap();
-        assert_eq!(format_card(&gisela).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&gisela).unwrap());
+        assert_eq!(
+            format_card(&gisela, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&gisela, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -698,8 +2193,14 @@ mod tests {
         \n\
         Illustrated by Jim Nelson".to_owned();
         let artificer_class: Card = Card::multiverse(567228).await.unwrap();
-        assert_eq!(format_card(&artificer_class).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&artificer_class).unwrap());
+        assert_eq!(
+            format_card(&artificer_class, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&artificer_class, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -714,10 +2215,13 @@ mod tests {
             .to_owned();
         let history_of_benalia: Card = Card::multiverse(442909).await.unwrap();
         assert_eq!(
-            format_card(&history_of_benalia).unwrap()[0],
+            format_card(&history_of_benalia, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&history_of_benalia).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&history_of_benalia, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -737,8 +2241,14 @@ mod tests {
         Illustrated by Eric Deschamps"
             .to_owned();
         let brazen_borrower: Card = Card::multiverse(473001).await.unwrap();
-        assert_eq!(format_card(&brazen_borrower).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&brazen_borrower).unwrap());
+        assert_eq!(
+            format_card(&brazen_borrower, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&brazen_borrower, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -753,8 +2263,14 @@ mod tests {
         Illustrated by Kekai Kotaki"
             .to_owned();
         let arcane_proxy: Card = Card::multiverse(583660).await.unwrap();
-        assert_eq!(format_card(&arcane_proxy).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&arcane_proxy).unwrap());
+        assert_eq!(
+            format_card(&arcane_proxy, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&arcane_proxy, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -768,8 +2284,14 @@ mod tests {
         Illustrated by Andrea Radeck"
             .to_owned();
         let adorable_kitten: Card = Card::multiverse(479485).await.unwrap();
-        assert_eq!(format_card(&adorable_kitten).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&adorable_kitten).unwrap());
+        assert_eq!(
+            format_card(&adorable_kitten, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&adorable_kitten, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -784,8 +2306,14 @@ mod tests {
         Illustrated by Andrea Radeck"
             .to_owned();
         let half_kitten_half: Card = Card::multiverse(439398).await.unwrap();
-        assert_eq!(format_card(&half_kitten_half).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&half_kitten_half).unwrap());
+        assert_eq!(
+            format_card(&half_kitten_half, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&half_kitten_half, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -800,8 +2328,11 @@ mod tests {
         let sheep = Card::scryfall_id("281d2c14-2343-44c9-a589-7f4da37978a2".parse().unwrap())
             .await
             .unwrap();
-        assert_eq!(format_card(&sheep).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&sheep).unwrap());
+        assert_eq!(
+            format_card(&sheep, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(None, get_artist(&sheep, &FormatOptions::default()).unwrap());
     }
 
     #[tokio::test]
@@ -820,12 +2351,14 @@ mod tests {
             Card::scryfall_id("9cd6a16f-1eff-4624-8f7f-4d9e70a694bb".parse().unwrap())
                 .await
                 .unwrap();
-        let result = format_card(&ajani_reversable).unwrap();
+        let result = format_card(&ajani_reversable, &FormatOptions::default()).unwrap();
         assert_eq!(result[0], face1);
         assert_eq!(result[1], face2);
         assert_eq!(
             Some("\n\nIllustrated by Fay Dalton"),
-            get_artist(&ajani_reversable).unwrap().as_deref()
+            get_artist(&ajani_reversable, &FormatOptions::default())
+                .unwrap()
+                .as_deref()
         );
     }
 
@@ -844,15 +2377,59 @@ mod tests {
             Card::scryfall_id("e2235007-b02e-463b-95e1-a8bea74a0f9d".parse().unwrap())
                 .await
                 .unwrap();
-        let result = format_card(&angel_angel).unwrap();
+        let result = format_card(&angel_angel, &FormatOptions::default()).unwrap();
         assert_eq!(result[0], face1);
         assert_eq!(result[1], face2);
         assert_eq!(
             Some("\n\nIllustrated by Magali Villeneuve"),
-            get_artist(&angel_angel).unwrap().as_deref()
+            get_artist(&angel_angel, &FormatOptions::default())
+                .unwrap()
+                .as_deref()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blank_empty_faces_angel_angel() {
+        let face1 = "Angel\n\
+        Token Creature — Angel\n\
+        Flying\n\
+        \n\
+        4/4"
+        .to_owned();
+        let angel_angel =
+            Card::scryfall_id("e2235007-b02e-463b-95e1-a8bea74a0f9d".parse().unwrap())
+                .await
+                .unwrap();
+        let result =
+            blank_empty_faces(format_card(&angel_angel, &FormatOptions::default()).unwrap());
+        assert_eq!(result, vec![face1, "".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_combine_face_texts_kytheon() {
+        let kytheon = Card::multiverse(398428).await.unwrap();
+        let card_texts = format_card(&kytheon, &FormatOptions::default()).unwrap();
+        let result = combine_face_texts(card_texts.clone(), DEFAULT_FACE_SEPARATOR);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0], format!("{}\n\n{}", card_texts[0], card_texts[1]));
+        assert_eq!(result[1], "");
+    }
+
+    #[test]
+    fn test_combine_face_texts_skips_empty_faces() {
+        let texts = vec!["Angel\nToken Creature".to_owned(), "".to_owned()];
+        assert_eq!(
+            combine_face_texts(texts, DEFAULT_FACE_SEPARATOR),
+            vec!["Angel\nToken Creature".to_owned(), "".to_owned()]
         );
     }
 
+    #[test]
+    fn test_blank_empty_faces_keeps_all_empty_faces() {
+        let texts = vec!["Angel\nToken".to_owned(), "Angel\nToken".to_owned()];
+        assert_eq!(blank_empty_faces(texts.clone()), texts);
+    }
+
     #[tokio::test]
     async fn test_format_sorin_emblem() {
         let expected_string = "Sorin, Lord of Innistrad Emblem\n\
@@ -865,8 +2442,14 @@ mod tests {
             Card::scryfall_id("327ddaaf-b6a7-4c80-9b38-5ab68181b3d6".parse().unwrap())
                 .await
                 .unwrap();
-        assert_eq!(format_card(&sorin_emblem).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&sorin_emblem).unwrap());
+        assert_eq!(
+            format_card(&sorin_emblem, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&sorin_emblem, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -881,10 +2464,13 @@ mod tests {
                 .await
                 .unwrap();
         assert_eq!(
-            format_card(&interplanar_tunnel).unwrap()[0],
+            format_card(&interplanar_tunnel, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&interplanar_tunnel).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&interplanar_tunnel, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -901,10 +2487,13 @@ mod tests {
                 .await
                 .unwrap();
         assert_eq!(
-            format_card(&academy_at_tolaria_west).unwrap()[0],
+            format_card(&academy_at_tolaria_west, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&academy_at_tolaria_west).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&academy_at_tolaria_west, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -923,8 +2512,11 @@ mod tests {
         let ertai = Card::scryfall_id("5cbb9b5d-9199-4a5b-957d-8fa681caeb7c".parse().unwrap())
             .await
             .unwrap();
-        assert_eq!(format_card(&ertai).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&ertai).unwrap());
+        assert_eq!(
+            format_card(&ertai, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(None, get_artist(&ertai, &FormatOptions::default()).unwrap());
     }
 
     #[tokio::test]
@@ -939,12 +2531,14 @@ mod tests {
             Card::scryfall_id("8de2ff37-fdb7-4f77-9d48-e99afac9a79e".parse().unwrap())
                 .await
                 .unwrap();
-        let result = format_card(&chillerpillar_art_card).unwrap();
+        let result = format_card(&chillerpillar_art_card, &FormatOptions::default()).unwrap();
         assert_eq!(result[0], face1);
         assert_eq!(result[1], face2);
         assert_eq!(
             Some("\n\nIllustrated by Suzanne Helmigh"),
-            get_artist(&chillerpillar_art_card).unwrap().as_deref()
+            get_artist(&chillerpillar_art_card, &FormatOptions::default())
+                .unwrap()
+                .as_deref()
         );
     }
 
@@ -969,13 +2563,15 @@ mod tests {
             Card::scryfall_id("b3af679b-6ee6-4a1d-8ec3-b659bdd90b4a".parse().unwrap())
                 .await
                 .unwrap();
-        let result = format_card(&invasion_of_fiora).unwrap();
+        let result = format_card(&invasion_of_fiora, &FormatOptions::default()).unwrap();
         println!("{:#?}", result);
         assert_eq!(result[0], face1);
         assert_eq!(result[1], face2);
         assert_eq!(
             Some("\n\nIllustrated by Joshua Raphael"),
-            get_artist(&invasion_of_fiora).unwrap().as_deref()
+            get_artist(&invasion_of_fiora, &FormatOptions::default())
+                .unwrap()
+                .as_deref()
         );
     }
 
@@ -993,10 +2589,13 @@ mod tests {
                 .await
                 .unwrap();
         assert_eq!(
-            format_card(&case_of_the_filched_falcon).unwrap()[0],
+            format_card(&case_of_the_filched_falcon, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&case_of_the_filched_falcon).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&case_of_the_filched_falcon, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -1014,10 +2613,13 @@ mod tests {
                 .await
                 .unwrap();
         assert_eq!(
-            format_card(&negan_the_cold_blooded).unwrap()[0],
+            format_card(&negan_the_cold_blooded, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&negan_the_cold_blooded).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&negan_the_cold_blooded, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -1035,10 +2637,13 @@ mod tests {
                 .await
                 .unwrap();
         assert_eq!(
-            format_card(&malik_grim_manipulator).unwrap()[0],
+            format_card(&malik_grim_manipulator, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&malik_grim_manipulator).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&malik_grim_manipulator, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -1058,10 +2663,13 @@ mod tests {
                 .await
                 .unwrap();
         assert_eq!(
-            format_card(&spacegodzilla_death_corona).unwrap()[0],
+            format_card(&spacegodzilla_death_corona, &FormatOptions::default()).unwrap()[0],
             expected_string
         );
-        assert_eq!(None, get_artist(&spacegodzilla_death_corona).unwrap());
+        assert_eq!(
+            None,
+            get_artist(&spacegodzilla_death_corona, &FormatOptions::default()).unwrap()
+        );
     }
 
     #[tokio::test]
@@ -1080,7 +2688,13 @@ mod tests {
             Card::scryfall_id("a1523cda-c47d-4419-a5d3-fd6ed9867c56".parse().unwrap())
                 .await
                 .unwrap();
-        assert_eq!(format_card(&void_beckoner).unwrap()[0], expected_string);
-        assert_eq!(None, get_artist(&void_beckoner).unwrap());
+        assert_eq!(
+            format_card(&void_beckoner, &FormatOptions::default()).unwrap()[0],
+            expected_string
+        );
+        assert_eq!(
+            None,
+            get_artist(&void_beckoner, &FormatOptions::default()).unwrap()
+        );
     }
 }
@@ -4,8 +4,15 @@
  * SPDX-License-Identifier: MIT
  */
 
-use image::{imageops::rotate90, io::Reader};
-use scryfall::card::{Card, Layout};
+use ab_glyph::{point, Font, FontRef, GlyphId, PxScale, PxScaleFont, ScaleFont};
+use image::{
+    imageops::{rotate90, FilterType},
+    io::Reader,
+    Rgb, RgbImage,
+};
+use log::{debug, warn};
+use scryfall::card::{Card, Finishes, ImageUris, Layout};
+use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use url::Url;
@@ -13,12 +20,24 @@ use url::Url;
 use crate::config::DailyScryConfig;
 use crate::error::{Error, Result};
 
+/// The generic back of Magic cards
+/// See: https://scryfall.com/docs/api/images
+const CARD_BACK_URI: &str =
+    "https://backs.scryfall.io/large/0/a/0aeebaf5-8c7d-4636-9e82-8c27447861f7.jpg";
+
+/// The name the card back is downloaded to, it is the same for every card
+const CARD_BACK_FILE_NAME: &str = "card_back_large.jpg";
+
 /// Downloads the images and returns a vector of file paths for a [`scryfall::card::Card`]
 ///
 /// # Arguments
 ///
 /// * `card` - A borrowed [`scryfall::card::Card`]
-pub async fn download_images(config: &DailyScryConfig, card: &Card) -> Result<Vec<PathBuf>> {
+pub async fn download_images(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    card: &Card,
+) -> Result<Vec<PathBuf>> {
     match card.layout.clone() {
         Layout::Normal
         | Layout::Meld
@@ -37,24 +56,224 @@ pub async fn download_images(config: &DailyScryConfig, card: &Card) -> Result<Ve
         | Layout::Split
         | Layout::Flip
         | Layout::Adventure
-        | Layout::Case => download_single_image(config, card).await,
+        | Layout::Case => download_single_image(config, client, card).await,
         Layout::Transform
         | Layout::ModalDfc
         | Layout::ReversibleCard
         | Layout::DoubleFacedToken
-        | Layout::ArtSeries => download_multiple_images(config, card).await,
+        | Layout::ArtSeries => download_multiple_images(config, client, card).await,
+        _ if config.strict_layouts => Err(Error::ImageNotFound),
+        _ if card.image_uris.is_some() => download_single_image(config, client, card).await,
+        _ if card.card_faces.is_some() => download_multiple_images(config, client, card).await,
         _ => Err(Error::ImageNotFound),
     }
 }
 
-async fn download_single_image(config: &DailyScryConfig, card: &Card) -> Result<Vec<PathBuf>> {
-    let image_uris: Result<Url> = card
+/// Downloads the images of the tokens and returns their file paths and descriptions
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens as returned by [`crate::card::tokens`]
+pub async fn download_token_images(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    tokens: &[Card],
+) -> Result<Vec<(PathBuf, String)>> {
+    let mut token_images = vec![];
+    for token in tokens {
+        let image_uri = token
+            .image_uris
+            .as_ref()
+            .and_then(|image_uris| select_card_image(image_uris, image_versions(config)))
+            .ok_or(Error::ImageNotFound)?;
+        let file_name = image_file_name(token, 0, &image_uri);
+        let path = download_file(config, client, image_uri, file_name).await?;
+        resize_image(config, &path)?;
+        token_images.push((path, format!("{} token", token.name)));
+    }
+    Ok(token_images)
+}
+
+/// Returns the [`Finishes`] for a value of `DAILY_SCRY_PREFER_FINISH`
+pub fn finish(value: &str) -> Option<Finishes> {
+    match value {
+        "nonfoil" => Some(Finishes::Nonfoil),
+        "foil" => Some(Finishes::Foil),
+        "etched" => Some(Finishes::Etched),
+        _ => None,
+    }
+}
+
+/// Selects the printing whose image should be used to show `card` in the given finish
+///
+/// Most printings share their image across finishes, so a printing is only selected if `card`
+/// itself lacks the finish and the printing looks different, i.e. has another illustration or
+/// frame effects. Otherwise the image of `card` is kept and `None` is returned.
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+/// * `prints` - The printings of the card
+/// * `finish` - The requested finish
+pub fn select_finish_printing<'a>(
+    card: &Card,
+    prints: &'a [Card],
+    finish: Finishes,
+) -> Option<&'a Card> {
+    if card.finishes.contains(&finish) {
+        return None;
+    }
+    prints.iter().find(|printing| {
+        printing.finishes.contains(&finish)
+            && (printing.illustration_id != card.illustration_id
+                || printing.frame_effects != card.frame_effects)
+    })
+}
+
+/// The font card texts are rendered with, see [`render_text_to_image`]
+const TEXT_IMAGE_FONT: &[u8] = include_bytes!("../assets/DejaVuSans.ttf");
+const TEXT_IMAGE_FONT_SIZE: f32 = 28.0;
+const TEXT_IMAGE_WIDTH: u32 = 800;
+const TEXT_IMAGE_MARGIN: u32 = 32;
+
+/// Renders a formatted card text to a png and returns its file path
+///
+/// The text is drawn black on white and wrapped to fit the width of the image. The height of the
+/// image grows with the number of lines.
+///
+/// # Arguments
+///
+/// * `text` - The text as returned by [`crate::format::format_card`]
+/// * `index` - The index of the text, used to name the file
+pub fn render_text_to_image(config: &DailyScryConfig, text: &str, index: usize) -> Result<PathBuf> {
+    let font = FontRef::try_from_slice(TEXT_IMAGE_FONT).map_err(|_| Error::TextImageFailed)?;
+    let font = font.as_scaled(PxScale::from(TEXT_IMAGE_FONT_SIZE));
+
+    let max_line_width = (TEXT_IMAGE_WIDTH - 2 * TEXT_IMAGE_MARGIN) as f32;
+    let lines = wrap_text(&font, text, max_line_width);
+    let line_height = font.height() + font.line_gap();
+    let height = 2 * TEXT_IMAGE_MARGIN + (lines.len() as f32 * line_height).ceil() as u32;
+
+    let mut image = RgbImage::from_pixel(TEXT_IMAGE_WIDTH, height, Rgb([255, 255, 255]));
+    for (line_index, line) in lines.iter().enumerate() {
+        let baseline = TEXT_IMAGE_MARGIN as f32 + font.ascent() + line_index as f32 * line_height;
+        draw_line(&mut image, &font, line, baseline);
+    }
+
+    let path = Path::new(&config.image_path).join(format!("text_{}.png", index));
+    image.save(&path).map_err(|_| Error::TextImageFailed)?;
+    Ok(path)
+}
+
+/// Splits `text` into lines that are at most `max_width` pixels wide
+///
+/// Lines are only broken between words, so a single word longer than `max_width` overflows.
+fn wrap_text(font: &PxScaleFont<&FontRef>, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in text.replace('\t', "    ").lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_owned()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if !line.is_empty() && line_width(font, &candidate) > max_width {
+                lines.push(line);
+                line = word.to_owned();
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn line_width(font: &PxScaleFont<&FontRef>, line: &str) -> f32 {
+    let mut width = 0.0;
+    let mut previous: Option<GlyphId> = None;
+    for character in line.chars() {
+        let glyph_id = font.glyph_id(character);
+        if let Some(previous) = previous {
+            width += font.kern(previous, glyph_id);
+        }
+        width += font.h_advance(glyph_id);
+        previous = Some(glyph_id);
+    }
+    width
+}
+
+fn draw_line(image: &mut RgbImage, font: &PxScaleFont<&FontRef>, line: &str, baseline: f32) {
+    let mut caret = TEXT_IMAGE_MARGIN as f32;
+    let mut previous: Option<GlyphId> = None;
+    for character in line.chars() {
+        let glyph_id = font.glyph_id(character);
+        if let Some(previous) = previous {
+            caret += font.kern(previous, glyph_id);
+        }
+        let glyph = glyph_id.with_scale_and_position(font.scale(), point(caret, baseline));
+        caret += font.h_advance(glyph_id);
+        previous = Some(glyph_id);
+
+        let Some(outlined_glyph) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined_glyph.px_bounds();
+        outlined_glyph.draw(|x, y, coverage| {
+            let x = bounds.min.x as i64 + x as i64;
+            let y = bounds.min.y as i64 + y as i64;
+            if x < 0 || y < 0 || x >= image.width() as i64 || y >= image.height() as i64 {
+                return;
+            }
+            let value = (255.0 * (1.0 - coverage.clamp(0.0, 1.0))) as u8;
+            let pixel = image.get_pixel_mut(x as u32, y as u32);
+            pixel.0 = pixel.0.map(|channel| channel.min(value));
+        });
+    }
+}
+
+/// Downloads the art crop of the card, or of its first face, and returns its file path
+///
+/// # Arguments
+///
+/// * `card` - A borrowed [`scryfall::card::Card`]
+pub async fn download_art_crop(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    card: &Card,
+) -> Result<PathBuf> {
+    let art_crop = card
         .image_uris
-        .clone()
-        .ok_or(Error::ImageNotFound)?
-        .png
-        .ok_or(Error::ImageNotFound);
-    let file_location = download_file(config, image_uris, None).await?;
+        .as_ref()
+        .and_then(|image_uris| image_uris.art_crop.clone())
+        .or_else(|| {
+            card.card_faces
+                .as_ref()
+                .and_then(|faces| faces.first())
+                .and_then(|face| face.image_uris.as_ref())
+                .and_then(|image_uris| image_uris.get("art_crop").cloned())
+        })
+        .ok_or(Error::ImageNotFound)?;
+    let file_name = image_file_name(card, 0, &art_crop);
+    let path = download_file(config, client, art_crop, file_name).await?;
+    resize_image(config, &path)?;
+    Ok(path)
+}
+
+async fn download_single_image(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    card: &Card,
+) -> Result<Vec<PathBuf>> {
+    let image_uri = select_card_image(
+        &card.image_uris.clone().ok_or(Error::ImageNotFound)?,
+        image_versions(config),
+    )
+    .ok_or(Error::ImageNotFound)?;
+    let file_name = image_file_name(card, 0, &image_uri);
+    let file_location = download_file(config, client, image_uri, file_name).await?;
+    resize_image(config, &file_location)?;
 
     let mut should_rotate = false;
     let layout = card.layout.clone();
@@ -64,64 +283,684 @@ async fn download_single_image(config: &DailyScryConfig, card: &Card) -> Result<
     }
 
     if layout == Layout::Split {
-        let oracle_text_second_face = card.card_faces.clone().unwrap()[1]
-            .oracle_text
-            .clone()
-            .unwrap();
+        let oracle_text_second_face = card
+            .card_faces
+            .as_ref()
+            .and_then(|faces| faces.get(1))
+            .and_then(|face| face.oracle_text.clone())
+            .unwrap_or_default();
         if !oracle_text_second_face.contains("Aftermath") {
             should_rotate = true;
         }
     }
 
     if should_rotate {
-        rotate_image(file_location.clone())?;
+        return Ok(vec![rotate_image(file_location)?]);
     }
 
     Ok(vec![file_location])
 }
 
-async fn download_multiple_images(config: &DailyScryConfig, card: &Card) -> Result<Vec<PathBuf>> {
-    let faces = card.card_faces.clone().unwrap();
-    let image_paths =
-        futures::future::join_all(faces.iter().enumerate().map(|(index, face)| async move {
-            let image_uri = face
-                .image_uris
-                .clone()
-                .unwrap_or_default()
-                .get("png")
-                .ok_or(Error::ImageNotFound)
-                .cloned();
-            download_file(config, image_uri, Some(format!("face_{}.png", index)))
-                .await
-                .unwrap()
-        }))
-        .await;
+async fn download_multiple_images(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    card: &Card,
+) -> Result<Vec<PathBuf>> {
+    let faces = card.card_faces.clone().ok_or(Error::ImageNotFound)?;
+    let face_count = faces.len().min(config.max_faces.unwrap_or(usize::MAX));
+    let mut image_paths = futures::future::join_all(faces.iter().take(face_count).enumerate().map(
+        |(index, face)| async move {
+            let image_uri = select_face_image(
+                &face.image_uris.clone().unwrap_or_default(),
+                image_versions(config),
+            )
+            .ok_or(Error::ImageNotFound)?;
+            let file_name = image_file_name(card, index, &image_uri);
+            download_file(config, client, image_uri, file_name).await
+        },
+    ))
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>>>()?;
+
+    for image_path in &image_paths {
+        resize_image(config, image_path)?;
+    }
 
-    if card.type_line.clone().unwrap().contains("Siege") {
-        rotate_image(image_paths[0].clone())?;
+    if let Some(first_image_path) = image_paths.first_mut().filter(|_| is_siege(card)) {
+        *first_image_path = rotate_image(first_image_path.clone())?;
     }
 
+    with_card_back(config, client, image_paths, CARD_BACK_URI).await
+}
+
+/// Appends the generic card back after the faces, if `DAILY_SCRY_INCLUDE_CARD_BACK` is set
+///
+/// # Arguments
+///
+/// * `config` - The [`DailyScryConfig`] to use
+/// * `image_paths` - The downloaded images of the faces
+/// * `card_back_uri` - Where the card back is downloaded from
+async fn with_card_back(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    mut image_paths: Vec<PathBuf>,
+    card_back_uri: &str,
+) -> Result<Vec<PathBuf>> {
+    if !config.include_card_back {
+        return Ok(image_paths);
+    }
+    let card_back_uri = Url::parse(card_back_uri).map_err(|_| Error::ImageNotFound)?;
+    let card_back = download_file(
+        config,
+        client,
+        card_back_uri,
+        CARD_BACK_FILE_NAME.to_owned(),
+    )
+    .await?;
+    resize_image(config, &card_back)?;
+    image_paths.push(card_back);
     Ok(image_paths)
 }
 
+/// Whether the front of the card is a sideways battle, cards without a type line are not
+fn is_siege(card: &Card) -> bool {
+    card.type_line
+        .as_ref()
+        .is_some_and(|type_line| type_line.contains("Siege"))
+}
+
+/// Returns the image versions to try, best first
+fn image_versions(config: &DailyScryConfig) -> Vec<&'static str> {
+    match config.image_version.as_str() {
+        "auto" => vec!["png", "large", "normal"],
+        _ => vec!["png"],
+    }
+}
+
+fn select_card_image(image_uris: &ImageUris, versions: Vec<&str>) -> Option<Url> {
+    versions.iter().find_map(|version| match *version {
+        "png" => image_uris.png.clone(),
+        "large" => image_uris.large.clone(),
+        "normal" => image_uris.normal.clone(),
+        _ => None,
+    })
+}
+
+fn select_face_image(image_uris: &HashMap<String, Url>, versions: Vec<&str>) -> Option<Url> {
+    versions
+        .iter()
+        .find_map(|version| image_uris.get(*version).cloned())
+}
+
+/// Builds the client every image is downloaded with, it times out after `DAILY_SCRY_HTTP_TIMEOUT_SECS`
+pub fn http_client(config: &DailyScryConfig) -> Result<reqwest::Client> {
+    let timeout = config.http_timeout();
+    let client = reqwest::Client::builder()
+        .connect_timeout(timeout)
+        .read_timeout(timeout)
+        .build()?;
+    Ok(client)
+}
+
+/// Returns a file name that is the same for every download of an image
+///
+/// It is made up of the scryfall id of the printing, the index of the face and the image
+/// version, which scryfall puts first in the path of its image uris, e.g. `…/png/front/…`.
+/// The printing id is used instead of the oracle id, because every printing of a card shares
+/// its oracle id but has its own art and frame. A cached image of a reprint, or of the full art
+/// printing that `DAILY_SCRY_PREFER_FULL_ART` picks, would otherwise be posted for another one.
+/// The extension is the one of the image uri, so resizing an image writes it with the encoder
+/// of its own format. Without one only the png version is assumed to be a png, scryfall serves
+/// the other versions as jpgs.
+fn image_file_name(card: &Card, face_index: usize, image_uri: &Url) -> String {
+    let version = image_uri
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .unwrap_or("image");
+    let extension = Path::new(image_uri.path())
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or(match version {
+            "png" => "png",
+            _ => "jpg",
+        });
+    format!("{}_{}_{}.{}", card.id, face_index, version, extension)
+}
+
+/// Downloads an image to `file_name` in the image directory
+///
+/// If the download fails and `DAILY_SCRY_FALLBACK_IMAGE` is set, a copy of the fallback image is
+/// returned instead, so a single missing image doesn't stop the post. The copy gets a name of its
+/// own, so a later run with `DAILY_SCRY_IMAGE_CACHE` tries to download the image again.
 async fn download_file(
     config: &DailyScryConfig,
-    image_uris: Result<Url>,
-    optional_file_name: Option<String>,
+    client: &reqwest::Client,
+    image_uri: Url,
+    file_name: String,
 ) -> Result<PathBuf> {
-    let file_name = optional_file_name.unwrap_or("test.png".to_string());
-    let response = reqwest::get(image_uris?).await?;
-    let path = Path::new(&config.image_path).join(file_name.clone());
-    let mut file = std::fs::File::create(path.clone())?;
+    let path = Path::new(&config.image_path).join(file_name);
+    if config.image_cache && path.exists() {
+        debug!("using cached image {}", path.display());
+        return Ok(path);
+    }
+    match fetch_file(client, image_uri.clone(), &path).await {
+        Ok(()) => Ok(path),
+        Err(error) => match &config.fallback_image {
+            Some(fallback_image) => {
+                warn!(
+                    "downloading {} failed ({}), using the fallback image {}",
+                    image_uri, error, fallback_image
+                );
+                let fallback_path = fallback_image_path(&path, Path::new(fallback_image));
+                std::fs::copy(fallback_image, &fallback_path)?;
+                Ok(fallback_path)
+            }
+            None => Err(error),
+        },
+    }
+}
+
+/// The path the fallback image is copied to instead of `path`, e.g. `fallback_{name}.png`
+///
+/// The extension is the one of the fallback image, since the copy keeps its format.
+fn fallback_image_path(path: &Path, fallback_image: &Path) -> PathBuf {
+    let file_stem = path
+        .file_stem()
+        .map(|file_stem| file_stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = fallback_image
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    path.with_file_name(format!("fallback_{}{}", file_stem, extension))
+}
+
+async fn fetch_file(client: &reqwest::Client, image_uri: Url, path: &Path) -> Result<()> {
+    let response = client.get(image_uri).send().await?.error_for_status()?;
+    let mut file = std::fs::File::create(path)?;
     let mut content = Cursor::new(response.bytes().await?);
     std::io::copy(&mut content, &mut file)?;
-    Ok(path)
+    Ok(())
+}
+
+/// Resizes a downloaded image to the bounds set by `DAILY_SCRY_MAX_IMAGE_DIMENSION` and
+/// `DAILY_SCRY_MIN_IMAGE_WIDTH`
+fn resize_image(config: &DailyScryConfig, image_path: &Path) -> Result<()> {
+    downscale_image(config, image_path)?;
+    upscale_image(config, image_path)
 }
 
-fn rotate_image(image_path: PathBuf) -> Result<()> {
-    let reader = Reader::open(image_path.clone())?;
+/// Upscales the image, if it is narrower than `DAILY_SCRY_MIN_IMAGE_WIDTH`
+///
+/// The aspect ratio is preserved.
+fn upscale_image(config: &DailyScryConfig, image_path: &Path) -> Result<()> {
+    let min_width = match config.min_image_width {
+        Some(min_width) => min_width as u32,
+        None => return Ok(()),
+    };
+    let dyn_img = Reader::open(image_path)?.with_guessed_format()?.decode()?;
+    if dyn_img.width() >= min_width {
+        return Ok(());
+    }
+    let height = (dyn_img.height() as f64 * min_width as f64 / dyn_img.width() as f64).round();
+    let resized_image = dyn_img.resize_exact(min_width, height as u32, FilterType::Lanczos3);
+    resized_image.save(image_path)?;
+    Ok(())
+}
+
+/// Downscales the image, if its longest side exceeds `DAILY_SCRY_MAX_IMAGE_DIMENSION`
+///
+/// The aspect ratio is preserved.
+fn downscale_image(config: &DailyScryConfig, image_path: &Path) -> Result<()> {
+    let max_dimension = match config.max_image_dimension {
+        Some(max_dimension) => max_dimension as u32,
+        None => return Ok(()),
+    };
+    let dyn_img = Reader::open(image_path)?.with_guessed_format()?.decode()?;
+    if dyn_img.width().max(dyn_img.height()) <= max_dimension {
+        return Ok(());
+    }
+    let resized_image = dyn_img.resize(max_dimension, max_dimension, FilterType::Lanczos3);
+    resized_image.save(image_path)?;
+    Ok(())
+}
+
+/// Rotates the image by 90 degrees and returns the path of the rotated image
+///
+/// The rotated image is saved next to the original as `{name}_rotated.png`, the original is kept.
+fn rotate_image(image_path: PathBuf) -> Result<PathBuf> {
+    let reader = Reader::open(image_path.clone())?.with_guessed_format()?;
     let dyn_img = reader.decode()?;
     let rotated_image = rotate90(&dyn_img);
-    rotated_image.save(image_path)?;
-    Ok(())
+    let rotated_path = rotated_image_path(&image_path);
+    rotated_image.save(&rotated_path)?;
+    Ok(rotated_path)
+}
+
+fn rotated_image_path(image_path: &Path) -> PathBuf {
+    let file_stem = image_path
+        .file_stem()
+        .map(|file_stem| file_stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    image_path.with_file_name(format!("{}_rotated.png", file_stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_select_card_image_falls_back_to_large() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let mut image_uris = grizzly_bears.image_uris.unwrap();
+        image_uris.png = None;
+        assert_eq!(select_card_image(&image_uris, vec!["png"]), None);
+        assert_eq!(
+            select_card_image(&image_uris, vec!["png", "large", "normal"]),
+            image_uris.large
+        );
+    }
+
+    #[test]
+    fn test_image_file_name() {
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let image_uri = grizzly_bears.image_uris.clone().unwrap().png.unwrap();
+
+        assert_eq!(
+            image_file_name(&grizzly_bears, 0, &image_uri),
+            format!("{}_0_png.png", grizzly_bears.id)
+        );
+
+        let large = grizzly_bears.image_uris.clone().unwrap().large.unwrap();
+        assert_eq!(
+            image_file_name(&grizzly_bears, 0, &large),
+            format!("{}_0_large.jpg", grizzly_bears.id)
+        );
+
+        let large_without_extension: Url = "https://cards.scryfall.io/large/front/a/b/ab"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            image_file_name(&grizzly_bears, 1, &large_without_extension),
+            format!("{}_1_large.jpg", grizzly_bears.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_file_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: Url = format!("http://{}/slow.png", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        let mut config = DailyScryConfig::default();
+        config.http_timeout_secs = Some(1);
+        let result = download_file(
+            &config,
+            &http_client(&config).unwrap(),
+            url,
+            "slow.png".to_owned(),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_download_file_cached() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: Url = format!(
+            "http://{}/png/front/cached.png",
+            listener.local_addr().unwrap()
+        )
+        .parse()
+        .unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0; 1024];
+                let _ = socket.read(&mut request).await;
+                socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\ntest",
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+        let mut config = DailyScryConfig::default();
+        config.image_path = std::env::temp_dir().display().to_string();
+        config.image_cache = true;
+        let file_name = "daily_scry_cached.png".to_owned();
+        let _ = std::fs::remove_file(std::env::temp_dir().join(&file_name));
+
+        let first_path = download_file(
+            &config,
+            &http_client(&config).unwrap(),
+            url.clone(),
+            file_name.clone(),
+        )
+        .await
+        .unwrap();
+        let second_path = download_file(&config, &http_client(&config).unwrap(), url, file_name)
+            .await
+            .unwrap();
+
+        assert_eq!(first_path, second_path);
+        assert_eq!(std::fs::read(&second_path).unwrap(), b"test");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_file_fallback_image() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: Url = format!("http://{}/missing.png", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let server_requests = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                server_requests.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0; 1024];
+                let _ = socket.read(&mut request).await;
+                socket
+                    .write_all(
+                        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+        let mut config = DailyScryConfig::default();
+        config.image_path = std::env::temp_dir().display().to_string();
+        let file_name = "daily_scry_missing.png".to_owned();
+
+        let result = download_file(
+            &config,
+            &http_client(&config).unwrap(),
+            url.clone(),
+            file_name.clone(),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let fallback_image = std::env::temp_dir().join("daily_scry_fallback.jpg");
+        std::fs::write(&fallback_image, b"fallback").unwrap();
+        config.fallback_image = Some(fallback_image.display().to_string());
+        config.image_cache = true;
+        let _ = std::fs::remove_file(std::env::temp_dir().join(&file_name));
+
+        let path = download_file(
+            &config,
+            &http_client(&config).unwrap(),
+            url.clone(),
+            file_name.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            path,
+            std::env::temp_dir().join("fallback_daily_scry_missing.jpg")
+        );
+        assert_eq!(std::fs::read(&path).unwrap(), b"fallback");
+        assert!(!std::env::temp_dir().join(&file_name).exists());
+
+        // a cached run downloads the image again instead of using the fallback
+        let requests_before = requests.load(Ordering::SeqCst);
+        let path = download_file(&config, &http_client(&config).unwrap(), url, file_name)
+            .await
+            .unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), requests_before + 1);
+        assert_eq!(
+            path,
+            std::env::temp_dir().join("fallback_daily_scry_missing.jpg")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_card_back() {
+        let mut config = DailyScryConfig::default();
+        config.image_path = std::env::temp_dir().display().to_string();
+        config.image_cache = true;
+        let card_back = std::env::temp_dir().join(CARD_BACK_FILE_NAME);
+        RgbImage::new(10, 14).save(&card_back).unwrap();
+        let faces = vec![PathBuf::from("face_0.png"), PathBuf::from("face_1.png")];
+
+        assert_eq!(
+            with_card_back(
+                &config,
+                &http_client(&config).unwrap(),
+                faces.clone(),
+                CARD_BACK_URI
+            )
+            .await
+            .unwrap(),
+            faces
+        );
+
+        config.include_card_back = true;
+        assert_eq!(
+            with_card_back(
+                &config,
+                &http_client(&config).unwrap(),
+                faces.clone(),
+                CARD_BACK_URI
+            )
+            .await
+            .unwrap(),
+            [faces, vec![card_back]].concat()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_multiple_images_max_faces() {
+        let mut config =
+            temp_env::with_vars([("DAILY_SCRY_MAX_FACES", Some("2"))], DailyScryConfig::new);
+        config.image_path = std::env::temp_dir().display().to_string();
+        config.image_cache = true;
+        let mut delver_of_secrets = Card::named("Delver of Secrets").await.unwrap();
+        let mut faces = delver_of_secrets.card_faces.clone().unwrap();
+        faces.push(faces[0].clone());
+        delver_of_secrets.card_faces = Some(faces.clone());
+        for (index, face) in faces.iter().enumerate() {
+            let image_uri =
+                select_face_image(&face.image_uris.clone().unwrap(), image_versions(&config))
+                    .unwrap();
+            let file_name = image_file_name(&delver_of_secrets, index, &image_uri);
+            RgbImage::new(10, 14)
+                .save(std::env::temp_dir().join(file_name))
+                .unwrap();
+        }
+
+        let image_paths =
+            download_multiple_images(&config, &http_client(&config).unwrap(), &delver_of_secrets)
+                .await
+                .unwrap();
+        assert_eq!(image_paths.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_is_siege_without_type_line() {
+        let mut invasion_of_ikoria = Card::named("Invasion of Ikoria").await.unwrap();
+        assert!(is_siege(&invasion_of_ikoria));
+
+        invasion_of_ikoria.type_line = None;
+        assert!(!is_siege(&invasion_of_ikoria));
+    }
+
+    #[test]
+    fn test_render_text_to_image() {
+        let mut config = DailyScryConfig::default();
+        config.image_path = std::env::temp_dir().display().to_string();
+        let text = "Grizzly Bears\t{1}{G}\nCreature — Bear\nDon't try to outrun one of Dominia's Grizzlies; it'll catch you, knock you down, and eat you. Of course, you could run up a tree. In that case you'll get a nice view before it knocks the tree down and eats you.\n2/2";
+
+        let image_path = render_text_to_image(&config, text, 0).unwrap();
+
+        assert!(std::fs::metadata(&image_path).unwrap().len() > 0);
+        let rendered_image = image::open(&image_path).unwrap().to_rgb8();
+        assert_eq!(rendered_image.width(), TEXT_IMAGE_WIDTH);
+        assert!(rendered_image.height() > 2 * TEXT_IMAGE_MARGIN);
+        assert!(rendered_image.pixels().any(|pixel| pixel.0 == [0, 0, 0]));
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        let font = FontRef::try_from_slice(TEXT_IMAGE_FONT).unwrap();
+        let font = font.as_scaled(PxScale::from(TEXT_IMAGE_FONT_SIZE));
+        let text = "Fireball\t{X}{R}\n\nThis spell costs {1} more to cast for each target beyond the first.";
+
+        let lines = wrap_text(&font, text, 400.0);
+
+        assert_eq!(lines[0], "Fireball    {X}{R}");
+        assert_eq!(lines[1], "");
+        assert!(lines.len() > 3);
+        assert!(lines.iter().all(|line| line_width(&font, line) <= 400.0));
+    }
+
+    #[test]
+    fn test_rotate_image_keeps_original() {
+        let image_path = std::env::temp_dir().join("daily_scry_rotate.png");
+        image::RgbImage::new(300, 200).save(&image_path).unwrap();
+
+        let rotated_path = rotate_image(image_path.clone()).unwrap();
+
+        assert_eq!(
+            rotated_path,
+            std::env::temp_dir().join("daily_scry_rotate_rotated.png")
+        );
+        let original_image = image::open(&image_path).unwrap();
+        assert_eq!(original_image.width(), 300);
+        let rotated_image = image::open(&rotated_path).unwrap();
+        assert_eq!(rotated_image.width(), 200);
+        assert_eq!(rotated_image.height(), 300);
+    }
+
+    #[test]
+    fn test_downscale_image() {
+        let image_path = std::env::temp_dir().join("daily_scry_downscale.png");
+        image::RgbImage::new(1500, 600).save(&image_path).unwrap();
+        let mut config = DailyScryConfig::default();
+        config.max_image_dimension = Some(500);
+
+        downscale_image(&config, &image_path).unwrap();
+
+        let downscaled_image = image::open(&image_path).unwrap();
+        assert_eq!(downscaled_image.width(), 500);
+        assert_eq!(downscaled_image.height(), 200);
+    }
+
+    #[test]
+    fn test_downscale_jpg_stays_jpg() {
+        let image_path = std::env::temp_dir().join("daily_scry_downscale.jpg");
+        image::RgbImage::new(1500, 600).save(&image_path).unwrap();
+        let mut config = DailyScryConfig::default();
+        config.max_image_dimension = Some(500);
+
+        downscale_image(&config, &image_path).unwrap();
+
+        let reader = Reader::open(&image_path)
+            .unwrap()
+            .with_guessed_format()
+            .unwrap();
+        assert_eq!(reader.format(), Some(image::ImageFormat::Jpeg));
+        assert_eq!(reader.decode().unwrap().width(), 500);
+    }
+
+    #[test]
+    fn test_downscale_image_unlimited() {
+        let image_path = std::env::temp_dir().join("daily_scry_downscale_unlimited.png");
+        image::RgbImage::new(1500, 600).save(&image_path).unwrap();
+        let mut config = DailyScryConfig::default();
+        config.max_image_dimension = None;
+
+        downscale_image(&config, &image_path).unwrap();
+
+        assert_eq!(image::open(&image_path).unwrap().width(), 1500);
+    }
+
+    #[tokio::test]
+    async fn test_select_finish_printing() {
+        let mut swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+        swamp.finishes = vec![Finishes::Nonfoil, Finishes::Foil];
+        swamp.frame_effects = None;
+        let mut etched_swamp = swamp.clone();
+        etched_swamp.finishes = vec![Finishes::Etched];
+        etched_swamp.frame_effects = Some(vec![scryfall::card::FrameEffect::Etched]);
+        let mut same_looking_swamp = swamp.clone();
+        same_looking_swamp.finishes = vec![Finishes::Etched];
+
+        let prints = vec![swamp.clone(), etched_swamp.clone()];
+        assert_eq!(
+            select_finish_printing(&swamp, &prints, Finishes::Etched),
+            Some(&etched_swamp)
+        );
+        assert_eq!(
+            select_finish_printing(&swamp, &prints, Finishes::Foil),
+            None
+        );
+        assert_eq!(
+            select_finish_printing(&swamp, &[same_looking_swamp], Finishes::Etched),
+            None
+        );
+    }
+
+    #[test]
+    fn test_finish() {
+        assert_eq!(finish("etched"), Some(Finishes::Etched));
+        assert_eq!(finish("glossy"), None);
+    }
+
+    #[test]
+    fn test_upscale_image() {
+        let image_path = std::env::temp_dir().join("daily_scry_upscale.png");
+        image::RgbImage::new(150, 60).save(&image_path).unwrap();
+        let mut config = DailyScryConfig::default();
+        config.min_image_width = Some(500);
+
+        upscale_image(&config, &image_path).unwrap();
+
+        let upscaled_image = image::open(&image_path).unwrap();
+        assert!(upscaled_image.width() >= 500);
+        assert_eq!(upscaled_image.height(), 200);
+    }
+
+    #[test]
+    fn test_upscale_image_wide_enough() {
+        let image_path = std::env::temp_dir().join("daily_scry_upscale_wide_enough.png");
+        image::RgbImage::new(600, 60).save(&image_path).unwrap();
+        let mut config = DailyScryConfig::default();
+        config.min_image_width = Some(500);
+
+        upscale_image(&config, &image_path).unwrap();
+
+        assert_eq!(image::open(&image_path).unwrap().width(), 600);
+    }
+
+    #[test]
+    fn test_select_face_image_falls_back_to_large() {
+        let large: Url = "https://cards.scryfall.io/large/front/a/b/ab.jpg"
+            .parse()
+            .unwrap();
+        let image_uris = HashMap::from([("large".to_owned(), large.clone())]);
+        assert_eq!(select_face_image(&image_uris, vec!["png"]), None);
+        assert_eq!(
+            select_face_image(&image_uris, vec!["png", "large", "normal"]),
+            Some(large)
+        );
+    }
 }
@@ -4,107 +4,437 @@
  * SPDX-License-Identifier: MIT
  */
 
-use crate::card::DefaultRandomCardGetter;
+use crate::card::{
+    build_filters, parse_decklist, CachedRulings, DecklistCardGetter, DefaultRandomCardGetter,
+    QueryRandomCardGetter, RulingsFetcher, ScryfallCardLookup, ScryfallCardResolver,
+    ScryfallRulingsFetcher,
+};
 use crate::config::cli_config::CLIConfig;
 use crate::config::DailyScryConfig;
-use crate::error::Result;
-use format::get_artist;
-use log::{debug, error, info, trace};
-use megalodon::megalodon::PostStatusOutput;
+use crate::error::{Error, Result};
+use format::{compose_post, get_artist, PostOptions};
+use log::{debug, error, info, trace, warn};
+use mastodon::MastodonTarget;
+use post_target::{PostTarget, RenderedPost};
 use scryfall::Card;
-use std::{path::PathBuf, process};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+use telegram::TelegramTarget;
 
 mod card;
 mod config;
 mod error;
+#[cfg(test)]
+mod fixtures;
 mod format;
 mod image;
 mod mastodon;
+mod post_target;
 mod telegram;
 mod util;
 
+/// The multiverse id of Grizzly Bears, which is posted by `--self-test`
+const SELF_TEST_MULTIVERSE_ID: usize = 155;
+const SELF_TEST_PREFIX: &str = "[DailyScry self-test]";
+
 #[tokio::main(flavor = "current_thread")]
-async fn main() -> Result<()> {
+async fn main() {
+    if let Err(error) = run().await {
+        error!("encountered error: {}", error);
+        eprintln!("Error: {}", error);
+        process::exit(error.exit_code());
+    }
+}
+
+async fn run() -> Result<()> {
+    let started = Instant::now();
     let cli_config = CLIConfig::new();
 
     if cli_config.dry_run {
         println!("dry run…")
     }
 
-    let config = DailyScryConfig::new();
+    let config = DailyScryConfig::from_env_file(cli_config.env_file.as_deref());
     config.validate()?;
 
-    let card = card::random_card(&config, DefaultRandomCardGetter()).await?;
+    if cli_config.list_filters {
+        for card_filter in build_filters(&config) {
+            println!(
+                "{}\t{}",
+                card_filter.name(),
+                card_filter.parameters(&config)
+            );
+        }
+        return Ok(());
+    }
 
-    let link = link(&card);
+    if let Some(json_card_path) = cli_config
+        .json_card
+        .as_ref()
+        .filter(|_| cli_config.format_only)
+    {
+        let card = read_json_card(json_card_path)?;
+        println!("{}", formatted_card(&config, &card)?);
+        return Ok(());
+    }
+
+    if let Some(ids_path) = &cli_config.export_ids {
+        return export_ids(&config, ids_path, cli_config.export_output.as_deref()).await;
+    }
 
-    let card_texts = format_card(&card);
+    let rulings = CachedRulings::new(ScryfallRulingsFetcher());
+    let interactive = cli_config.interactive
+        && !cli_config.dry_run
+        && !cli_config.fetch_only
+        && !cli_config.self_test;
 
-    let artist = get_artist(&card)?;
+    let card = if cli_config.self_test {
+        info!("running self-test…");
+        Card::multiverse(SELF_TEST_MULTIVERSE_ID).await?
+    } else {
+        loop {
+            let card = select_card(&config, &cli_config).await?;
 
-    if !cli_config.mastodon && !cli_config.telegram {
-        println!(
-            "{}{}\n\n{}",
-            card_texts.join("\n\n"),
-            artist.clone().unwrap_or_default(),
-            link
-        );
+            let card = if config.prefer_full_art {
+                card::with_full_art_printing(&card).await?
+            } else {
+                card
+            };
+
+            let card = match config.prefer_finish.as_deref().and_then(image::finish) {
+                Some(finish) => card::with_finish_printing(&card, finish).await?,
+                None => card,
+            };
+
+            let card = if config.use_printed_text {
+                card::with_printed_text(&card)
+            } else {
+                card
+            };
+
+            if !interactive {
+                break card;
+            }
+
+            println!("{}", preview(&config, &card, &rulings).await?);
+            match ask_to_post() {
+                Answer::Post => break card,
+                Answer::Reroll => continue,
+                Answer::Abort => {
+                    println!("aborted");
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    publish(&config, &cli_config, &card, &rulings, interactive, started).await
+}
+
+/// Formats, downloads and posts the selected card
+///
+/// With `--fetch-only` the card is only printed, before any of these steps.
+async fn publish<F: RulingsFetcher>(
+    config: &DailyScryConfig,
+    cli_config: &CLIConfig,
+    card: &Card,
+    rulings: &CachedRulings<F>,
+    interactive: bool,
+    started: Instant,
+) -> Result<()> {
+    if cli_config.fetch_only {
+        println!("{}", fetched_card(card));
+        return Ok(());
+    }
+
+    let link = link(card);
+
+    let card_texts = format_card(config, card)?;
+    let card_texts = if cli_config.self_test {
+        self_test_texts(card_texts)
+    } else {
+        card_texts
+    };
+
+    let artist = get_artist(card, &config.format_options())?;
+
+    let price = config
+        .show_price
+        .as_deref()
+        .and_then(|currency| format::get_price(card, currency));
+
+    let backends = Backends::resolve(cli_config, config);
+
+    if !backends.mastodon && !backends.telegram {
+        // an interactive run already showed the preview before asking to post
+        if !interactive {
+            println!("{}", preview(config, card, rulings).await?);
+        }
+        info!("{}", summary(card, 0, &[], started.elapsed()));
         return Ok(());
     }
 
     if cli_config.dry_run {
         debug!("This was a dry run. Exiting…");
-        process::exit(0)
+        info!("{}", summary(card, 0, &[], started.elapsed()));
+        return Ok(());
     }
 
-    let image_paths = download_image(&config, &card).await?;
+    let targets = post_targets(&backends, config)?;
 
-    if cli_config.mastodon {
-        config.check_mastodon_config()?;
-        post_to_mastodon(
-            &config,
-            card_texts.clone(),
-            artist.clone(),
-            image_paths.clone(),
-            link,
-        )
-        .await?;
+    // a single client for all downloads, so they share its connection pool
+    let client = image::http_client(config)?;
+
+    let image_paths = download_image(config, &client, card).await?;
+
+    let token_images = if config.include_tokens {
+        let tokens = card::tokens(card).await?;
+        image::download_token_images(config, &client, &tokens).await?
+    } else {
+        vec![]
+    };
+
+    let text_images = if config.render_text_image {
+        render_text_images(config, &card_texts)?
+    } else {
+        vec![]
+    };
+
+    let teaser = if config.teaser {
+        Some(image::download_art_crop(config, &client, card).await?)
+    } else {
+        None
+    };
+
+    let post = RenderedPost {
+        name: card.name.clone(),
+        card_texts,
+        artist,
+        price,
+        notes: notes(config, card, rulings).await,
+        provenance: Some(format::get_provenance(card, config.use_printed_text))
+            .filter(|_| config.alt_text_include_set),
+        images: image_paths,
+        text_images,
+        token_images,
+        teaser,
+        link: link.to_owned(),
+        sensitive: card.content_warning,
+        extra_hashtags: [
+            Some(format::year_hashtag(card))
+                .filter(|_| config.year_hashtag)
+                .into_iter()
+                .collect(),
+            if config.artist_hashtags {
+                format::artist_hashtags(card)
+            } else {
+                vec![]
+            },
+        ]
+        .concat(),
+        weekday_hashtags: config
+            .weekday_hashtags
+            .as_ref()
+            .map(|weekday_hashtags| format::weekday_hashtags(weekday_hashtags, config.today()))
+            .unwrap_or_default(),
+        oracle_id: format::get_oracle_id(card).filter(|_| config.show_oracle_id),
+        header: header(config),
+    };
+
+    post_target::post_to_all(&targets, &post).await?;
+
+    let image_count = post.images.len()
+        + post.text_images.len()
+        + post.token_images.len()
+        + post.teaser.iter().count();
+    let target_names = targets
+        .iter()
+        .map(|target| target.name())
+        .collect::<Vec<_>>();
+    info!(
+        "{}",
+        summary(card, image_count, &target_names, started.elapsed())
+    );
+    Ok(())
+}
+
+/// The backends that are posted to
+#[derive(Debug, PartialEq)]
+struct Backends {
+    mastodon: bool,
+    telegram: bool,
+}
+
+impl Backends {
+    /// Backends given on the command line take precedence over the ones enabled in the config
+    ///
+    /// In safe mode no backend is enabled, regardless of the command line.
+    fn resolve(cli_config: &CLIConfig, config: &DailyScryConfig) -> Self {
+        if config.safe_mode {
+            warn!("SAFE_MODE active, not posting");
+            return Backends {
+                mastodon: false,
+                telegram: false,
+            };
+        }
+        if cli_config.mastodon || cli_config.telegram {
+            return Backends {
+                mastodon: cli_config.mastodon,
+                telegram: cli_config.telegram,
+            };
+        }
+        Backends {
+            mastodon: config.enable_mastodon,
+            telegram: config.enable_telegram,
+        }
     }
+}
 
-    if cli_config.telegram {
-        config.check_telegram_config()?;
-        post_to_telegram(
-            &config,
-            card_texts.clone(),
-            artist.clone(),
-            image_paths.clone(),
-            link,
-        )
-        .await?;
+/// Returns the targets of the enabled backends
+///
+/// If `DAILY_SCRY_SKIP_MISCONFIGURED_BACKENDS` is set, misconfigured backends are logged and
+/// skipped, as long as at least one backend is left.
+fn post_targets<'a>(
+    backends: &Backends,
+    config: &'a DailyScryConfig,
+) -> Result<Vec<Box<dyn PostTarget + 'a>>> {
+    let mut candidates: Vec<(&str, Result<Box<dyn PostTarget + 'a>>)> = vec![];
+    if backends.mastodon {
+        candidates.push((
+            "mastodon",
+            MastodonTarget::new(config).map(|target| Box::new(target) as Box<dyn PostTarget>),
+        ));
+    }
+    if backends.telegram {
+        candidates.push((
+            "telegram",
+            TelegramTarget::new(config).map(|target| Box::new(target) as Box<dyn PostTarget>),
+        ));
     }
 
-    Ok(())
+    let mut targets = vec![];
+    let mut first_error = None;
+    for (name, target) in candidates {
+        match target {
+            Ok(target) => targets.push(target),
+            Err(error) if config.skip_misconfigured_backends => {
+                warn!("skipping {}, it is misconfigured: {}", name, error);
+                first_error.get_or_insert(error);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    match first_error {
+        Some(error) if targets.is_empty() => Err(error),
+        _ => Ok(targets),
+    }
 }
 
-async fn download_image(config: &DailyScryConfig, card: &Card) -> Result<Vec<PathBuf>> {
+async fn select_card(config: &DailyScryConfig, cli_config: &CLIConfig) -> Result<Card> {
+    let verbose_card = cli_config.verbose_card;
+    let today = config.today();
+
+    if cli_config.on_this_day {
+        let query = card::query_for_day(today);
+        info!("using query '{}' for {}", query, today.format("%B %-d"));
+        match card::random_card(config, QueryRandomCardGetter::new(query), verbose_card).await {
+            Ok(card) => return Ok(card),
+            Err(error) => warn!(
+                "no card was released on {} ({}), falling back to a random card",
+                today.format("%B %-d"),
+                error
+            ),
+        }
+    }
+
+    if let Some(decklist_path) = &config.decklist_path {
+        let entries = fs::read_to_string(decklist_path)
+            .map(|decklist| parse_decklist(&decklist))
+            .ok()
+            .filter(|entries| !entries.is_empty())
+            .ok_or(Error::ReadConfiguration {
+                key: "DAILY_SCRY_DECKLIST_PATH".to_string(),
+            })?;
+        info!("using decklist {}", decklist_path);
+        let card_getter = DecklistCardGetter::new(entries, ScryfallCardResolver());
+        return card::random_card(config, card_getter, verbose_card).await;
+    }
+
+    if let Some(commander) = &config.commander {
+        let commander = Card::named(commander).await?;
+        let query = card::query_for_commander(&commander.color_identity);
+        info!("using query '{}' for commander {}", query, commander.name);
+        return card::random_card(config, QueryRandomCardGetter::new(query), verbose_card).await;
+    }
+
+    match card::query_for_weekday(config, today) {
+        Some(query) => {
+            info!("using query '{}' for {}", query, today.format("%A"));
+            card::random_card(config, QueryRandomCardGetter::new(query), verbose_card).await
+        }
+        None => card::random_card(config, DefaultRandomCardGetter(), verbose_card).await,
+    }
+}
+
+async fn download_image(
+    config: &DailyScryConfig,
+    client: &reqwest::Client,
+    card: &Card,
+) -> Result<Vec<PathBuf>> {
     trace!("downloading card images…");
-    let image_paths = image::download_images(&config, &card).await?;
+    let image_paths = image::download_images(config, client, card).await?;
     debug!("downloaded card images {:?}", image_paths);
     Ok(image_paths)
 }
 
-fn format_card(card: &Card) -> Vec<String> {
-    return match format::format_card(&card) {
-        Err(error) => {
-            error!("encountered error: {}", error);
-            process::exit(1)
-        }
-        Ok(texts) => {
-            info!("got card texts.");
-            debug!("card texts {:?}", texts);
-            texts
+/// Renders every non-empty card text to an image, described by the text itself
+fn render_text_images(
+    config: &DailyScryConfig,
+    card_texts: &[String],
+) -> Result<Vec<(PathBuf, String)>> {
+    card_texts
+        .iter()
+        .enumerate()
+        .filter(|(_, card_text)| !card_text.is_empty())
+        .map(|(index, card_text)| {
+            image::render_text_to_image(config, card_text, index)
+                .map(|image_path| (image_path, card_text.clone()))
+        })
+        .collect()
+}
+
+fn format_card(config: &DailyScryConfig, card: &Card) -> Result<Vec<String>> {
+    let result = match format::format_card(&card, &config.format_options()) {
+        Err(Error::UnknownCardLayout { layout }) if !config.strict_layouts => {
+            warn!(
+                "card layout {:?} is not known, falling back to generic formatting",
+                layout
+            );
+            format::format_generic(&card, &config.format_options())
         }
+        result => result,
     };
+
+    let result = match result {
+        Ok(card_texts) if config.skip_empty_faces => Ok(format::blank_empty_faces(card_texts)),
+        result => result,
+    };
+
+    let texts = result?;
+    info!("got card texts.");
+    debug!("card texts {:?}", texts);
+    Ok(texts)
+}
+
+/// The header of the first post, if `DAILY_SCRY_POST_HEADER` is set
+fn header(config: &DailyScryConfig) -> Option<String> {
+    config
+        .post_header
+        .as_deref()
+        .map(|template| format::post_header(template, config.today()))
 }
 
 fn link(card: &Card) -> &str {
@@ -113,36 +443,485 @@ fn link(card: &Card) -> &str {
     link
 }
 
-async fn post_to_mastodon(
+async fn preview<F: RulingsFetcher>(
     config: &DailyScryConfig,
-    card_texts: Vec<String>,
-    artist: Option<String>,
-    image_paths: Vec<PathBuf>,
-    link: &str,
-) -> Result<()> {
-    debug!("creatiung mastodon post…");
-    let outputs = mastodon::post(&config, card_texts, artist, image_paths, link).await?;
-
-    for output in outputs {
-        match output {
-            PostStatusOutput::Status(status) => println!("Posted: {}", status.url.unwrap()),
-            PostStatusOutput::ScheduledStatus(scheduled_status) => {
-                println!("Will post at {}", scheduled_status.scheduled_at)
-            }
+    card: &Card,
+    rulings: &CachedRulings<F>,
+) -> Result<String> {
+    let card_texts = format_card(config, card)?;
+    let artist = get_artist(card, &config.format_options())?;
+    let options = PostOptions {
+        price: config
+            .show_price
+            .as_deref()
+            .and_then(|currency| format::get_price(card, currency)),
+        notes: notes(config, card, rulings).await,
+        links: vec![link(card).to_owned()],
+        oracle_id: format::get_oracle_id(card).filter(|_| config.show_oracle_id),
+        ..Default::default()
+    };
+    Ok(compose_post(
+        &format::with_header(&non_empty(&card_texts).join("\n\n"), &header(config)),
+        &artist,
+        &options,
+    ))
+}
+
+enum Answer {
+    Post,
+    Reroll,
+    Abort,
+}
+
+fn ask_to_post() -> Answer {
+    print!("post? [y/N/r(eroll)] ");
+    io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return Answer::Abort;
+    }
+
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Answer::Post,
+        "r" | "reroll" => Answer::Reroll,
+        _ => Answer::Abort,
+    }
+}
+
+async fn notes<F: RulingsFetcher>(
+    config: &DailyScryConfig,
+    card: &Card,
+    rulings: &CachedRulings<F>,
+) -> Vec<String> {
+    let mut notes = format::get_notes(card, config.show_story_spotlight, config.show_watermark);
+    if config.show_edhrec_rank {
+        notes.extend(format::get_edhrec_rank(card));
+    }
+    if config.show_rulings_count {
+        match rulings.rulings(card).await {
+            Ok(rulings) => notes.extend(format::get_rulings_count(&rulings)),
+            Err(error) => warn!("leaving out the rulings count: {}", error),
         }
     }
-    Ok(())
+    notes
 }
 
-async fn post_to_telegram(
+/// Exports the formatted texts and artists of the cards in the ids file as JSON
+///
+/// Ids that can't be looked up or cards that can't be formatted are logged and skipped.
+async fn export_ids(
     config: &DailyScryConfig,
-    card_texts: Vec<String>,
-    artist: Option<String>,
-    image_paths: Vec<PathBuf>,
-    link: &str,
+    ids_path: &Path,
+    output_path: Option<&Path>,
 ) -> Result<()> {
-    debug!("creatiung telegram post…");
-    telegram::post(config, card_texts, artist, image_paths, link).await?;
-    println!("Posted to {}", config.telegram_chat_id.clone().unwrap());
+    let ids = fs::read_to_string(ids_path)
+        .map(|content| card::parse_ids(&content))
+        .map_err(|_| Error::ReadConfiguration {
+            key: "--export-ids".to_string(),
+        })?;
+    info!("exporting {} ids from {}", ids.len(), ids_path.display());
+
+    let cards = card::lookup_cards(config, ScryfallCardLookup(), &ids).await;
+    let entries = cards
+        .iter()
+        .filter_map(|(id, card)| match export_entry(config, id, card) {
+            Ok(entry) => Some(entry),
+            Err(error) => {
+                warn!("skipping {}: {}", id, error);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    let export = serde_json::to_string_pretty(&entries)?;
+
+    match output_path {
+        Some(output_path) => {
+            fs::write(output_path, export).map_err(|_| Error::ReadConfiguration {
+                key: "--export-output".to_string(),
+            })?;
+            println!(
+                "Exported {} cards to {}",
+                entries.len(),
+                output_path.display()
+            );
+        }
+        None => println!("{}", export),
+    }
     Ok(())
 }
+
+/// The formatted texts and artist of a card, as exported by `--export-ids`
+fn export_entry(config: &DailyScryConfig, id: &str, card: &Card) -> Result<serde_json::Value> {
+    let artist = get_artist(card, &config.format_options())?;
+    Ok(serde_json::json!({
+        "id": id,
+        "name": card.name,
+        "texts": non_empty(&format_card(config, card)?),
+        "artist": artist.map(|artist| artist.trim().to_owned()),
+    }))
+}
+
+/// Reads a [`scryfall::Card`] in the JSON scryfall returns, from stdin if `path` is `-`
+fn read_json_card(path: &Path) -> Result<Card> {
+    let content = if path.as_os_str() == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(path)
+    };
+    let json_card_error = || Error::ReadConfiguration {
+        key: "--json-card".to_string(),
+    };
+    let content = content.map_err(|_| json_card_error())?;
+    serde_json::from_str(&content).map_err(|error| {
+        error!("could not parse the card of {}: {}", path.display(), error);
+        json_card_error()
+    })
+}
+
+/// The card texts and artist of the card, as printed by `--format-only`
+fn formatted_card(config: &DailyScryConfig, card: &Card) -> Result<String> {
+    let card_texts = format_card(config, card)?;
+    let artist = get_artist(card, &config.format_options())?;
+    Ok(compose_post(
+        &non_empty(&card_texts).join("\n\n"),
+        &artist,
+        &PostOptions::default(),
+    ))
+}
+
+/// The name and oracle id of the card, as printed by `--fetch-only`
+fn fetched_card(card: &Card) -> String {
+    match card.oracle_id {
+        Some(oracle_id) => format!("{}\t{}", card.name, oracle_id),
+        None => card.name.clone(),
+    }
+}
+
+/// A single line summing up a run, for operators auditing the logs
+///
+/// # Arguments
+///
+/// * `card` - The posted card
+/// * `image_count` - How many images were posted
+/// * `backends` - The names of the backends that were posted to
+/// * `elapsed` - How long the run took
+fn summary(card: &Card, image_count: usize, backends: &[&str], elapsed: Duration) -> String {
+    format!(
+        "summary: name=\"{}\" oracle_id={} layout={:?} images={} backends={} elapsed_ms={}",
+        card.name,
+        card.oracle_id
+            .map(|oracle_id| oracle_id.to_string())
+            .unwrap_or_else(|| "none".to_owned()),
+        card.layout,
+        image_count,
+        if backends.is_empty() {
+            "none".to_owned()
+        } else {
+            backends.join(",")
+        },
+        elapsed.as_millis()
+    )
+}
+
+/// Prepends the self-test label to the first text, so the post is recognizable as a test
+fn self_test_texts(card_texts: Vec<String>) -> Vec<String> {
+    card_texts
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| match index {
+            0 => format!("{}\n{}", SELF_TEST_PREFIX, text),
+            _ => text,
+        })
+        .collect()
+}
+
+fn non_empty(card_texts: &[String]) -> Vec<String> {
+    card_texts
+        .iter()
+        .filter(|text| !text.is_empty())
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::card_fixture;
+    use async_trait::async_trait;
+    use clap::Parser;
+    use scryfall::ruling::Ruling;
+
+    fn resolve(args: &[&str], enable_mastodon: bool, enable_telegram: bool) -> Backends {
+        let cli_config = CLIConfig::parse_from([&["daily_scry"], args].concat());
+        temp_env::with_vars(
+            [
+                (
+                    "DAILY_SCRY_ENABLE_MASTODON",
+                    Some(enable_mastodon.to_string()),
+                ),
+                (
+                    "DAILY_SCRY_ENABLE_TELEGRAM",
+                    Some(enable_telegram.to_string()),
+                ),
+            ],
+            || Backends::resolve(&cli_config, &DailyScryConfig::new()),
+        )
+    }
+
+    #[test]
+    fn test_backends_safe_mode() {
+        let cli_config = CLIConfig::parse_from(["daily_scry", "--mastodon", "--telegram"]);
+        let config = DailyScryConfig::default();
+        assert_eq!(
+            Backends::resolve(&cli_config, &config),
+            Backends {
+                mastodon: true,
+                telegram: true
+            }
+        );
+
+        let mut config = DailyScryConfig::default();
+        config.safe_mode = true;
+        config.enable_mastodon = true;
+        assert_eq!(
+            Backends::resolve(&cli_config, &config),
+            Backends {
+                mastodon: false,
+                telegram: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_skip_misconfigured_backends() {
+        let backends = Backends {
+            mastodon: true,
+            telegram: true,
+        };
+        let mut config = DailyScryConfig::default();
+        config.telegram_token = Some("token".to_owned());
+        config.telegram_chat_id = Some("@daily_scry".to_owned());
+        config.telegram_character_limit = Some(4096);
+
+        assert!(matches!(
+            post_targets(&backends, &config),
+            Err(Error::ReadConfiguration { key }) if key == "DAILY_SCRY_MASTODON_URL"
+        ));
+
+        config.skip_misconfigured_backends = true;
+        let names = post_targets(&backends, &config)
+            .unwrap()
+            .iter()
+            .map(|target| target.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["telegram"]);
+
+        config.telegram_token = None;
+        assert!(matches!(
+            post_targets(&backends, &config),
+            Err(Error::ReadConfiguration { key }) if key == "DAILY_SCRY_MASTODON_URL"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetched_card() {
+        let mut swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            fetched_card(&swamp),
+            "Swamp\t56719f6a-1a6c-4c0a-8d21-18f7d7350b68"
+        );
+        swamp.oracle_id = None;
+        assert_eq!(fetched_card(&swamp), "Swamp");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_only_skips_publishing() {
+        let mut config = DailyScryConfig::default();
+        config.enable_mastodon = true;
+        let rulings = CachedRulings::new(FailingRulingsFetcher());
+        // neither formatted, nor downloaded, nor posted without failing
+        let mut card = card_fixture("grizzly_bears");
+        card.type_line = None;
+        card.image_uris = None;
+
+        let cli_config = CLIConfig::parse_from(["daily_scry", "--fetch-only"]);
+        assert!(
+            publish(&config, &cli_config, &card, &rulings, false, Instant::now())
+                .await
+                .is_ok()
+        );
+
+        let cli_config = CLIConfig::parse_from(["daily_scry"]);
+        assert!(matches!(
+            publish(&config, &cli_config, &card, &rulings, false, Instant::now()).await,
+            Err(Error::TextNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_returns_before_posting() {
+        let mut config = DailyScryConfig::default();
+        config.enable_mastodon = true;
+        let rulings = CachedRulings::new(FailingRulingsFetcher());
+        let cli_config = CLIConfig::parse_from(["daily_scry", "--dry-run"]);
+
+        assert!(publish(
+            &config,
+            &cli_config,
+            &card_fixture("grizzly_bears"),
+            &rulings,
+            false,
+            Instant::now()
+        )
+        .await
+        .is_ok());
+    }
+
+    #[test]
+    fn test_format_only_json_card() {
+        let config = DailyScryConfig::default();
+        let grizzly_bears = card_fixture("grizzly_bears");
+        let path = std::env::temp_dir().join("daily_scry_grizzly_bears.json");
+        fs::write(&path, serde_json::to_string(&grizzly_bears).unwrap()).unwrap();
+
+        let card = read_json_card(&path).unwrap();
+        let output = formatted_card(&config, &card).unwrap();
+
+        assert_eq!(card, grizzly_bears);
+        assert!(output.starts_with("Grizzly Bears\t{1}{G}\nCreature — Bear"));
+        assert!(output.ends_with("Illustrated by Jeff A. Menges"));
+    }
+
+    #[test]
+    fn test_read_json_card_invalid() {
+        let path = std::env::temp_dir().join("daily_scry_invalid_card.json");
+        fs::write(&path, "{\"object\": \"card\"}").unwrap();
+        assert!(matches!(
+            read_json_card(&path),
+            Err(Error::ReadConfiguration { key }) if key == "--json-card"
+        ));
+        assert!(read_json_card(&PathBuf::from("/does/not/exist.json")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_entry() {
+        let grizzly_bears = Card::multiverse(155).await.unwrap();
+        let entry = export_entry(&DailyScryConfig::default(), "155", &grizzly_bears).unwrap();
+        assert_eq!(entry["id"], "155");
+        assert_eq!(entry["name"], "Grizzly Bears");
+        assert_eq!(entry["artist"], "Illustrated by Jeff A. Menges");
+        assert_eq!(entry["texts"].as_array().unwrap().len(), 1);
+    }
+
+    struct FailingRulingsFetcher();
+
+    #[async_trait(?Send)]
+    impl RulingsFetcher for FailingRulingsFetcher {
+        async fn fetch_rulings(&self, _: &Card) -> Result<Vec<Ruling>> {
+            Err(Error::Timeout)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notes_without_rulings() {
+        let mut config = DailyScryConfig::default();
+        config.show_rulings_count = true;
+        let fireball = Card::multiverse(197).await.unwrap();
+        let rulings = CachedRulings::new(FailingRulingsFetcher());
+
+        assert_eq!(
+            notes(&config, &fireball, &rulings).await,
+            format::get_notes(&fireball, false, false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backend_budgets() {
+        let mut config = DailyScryConfig::default();
+        config.mastodon_character_limit = Some(500);
+        config.telegram_character_limit = Some(60);
+        let fireball = Card::multiverse(197).await.unwrap();
+        let text = format_card(&config, &fireball).unwrap().join("\n");
+        let artist = get_artist(&fireball, &config.format_options()).unwrap();
+        let options = PostOptions::default();
+
+        let mastodon_posts =
+            format::compose_thread(&text, &artist, &options, mastodon::budget(&config));
+        let telegram_posts =
+            format::compose_thread(&text, &artist, &options, telegram::budget(&config));
+
+        assert_eq!(mastodon_posts.len(), 1);
+        assert!(telegram_posts.len() > 1);
+        assert!(telegram_posts.iter().all(|post| post.chars().count() <= 60));
+    }
+
+    #[tokio::test]
+    async fn test_summary() {
+        let mut swamp = Card::scryfall_id("ddaa0be1-7358-4ea2-8c40-be6d699a6631".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            summary(
+                &swamp,
+                1,
+                &["mastodon", "telegram"],
+                Duration::from_millis(1234)
+            ),
+            "summary: name=\"Swamp\" oracle_id=56719f6a-1a6c-4c0a-8d21-18f7d7350b68 layout=Normal images=1 backends=mastodon,telegram elapsed_ms=1234"
+        );
+
+        swamp.oracle_id = None;
+        assert_eq!(
+            summary(&swamp, 0, &[], Duration::from_millis(5)),
+            "summary: name=\"Swamp\" oracle_id=none layout=Normal images=0 backends=none elapsed_ms=5"
+        );
+    }
+
+    #[test]
+    fn test_self_test_texts() {
+        assert_eq!(
+            self_test_texts(vec!["Front".to_owned(), "Back".to_owned()]),
+            vec!["[DailyScry self-test]\nFront".to_owned(), "Back".to_owned()]
+        );
+        assert_eq!(self_test_texts(vec![]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_backends_from_config() {
+        assert_eq!(
+            resolve(&[], true, false),
+            Backends {
+                mastodon: true,
+                telegram: false
+            }
+        );
+        assert_eq!(
+            resolve(&[], false, false),
+            Backends {
+                mastodon: false,
+                telegram: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_backends_cli_takes_precedence() {
+        assert_eq!(
+            resolve(&["--telegram"], true, false),
+            Backends {
+                mastodon: false,
+                telegram: true
+            }
+        );
+        assert_eq!(
+            resolve(&["--mastodon", "--telegram"], false, false),
+            Backends {
+                mastodon: true,
+                telegram: true
+            }
+        );
+    }
+}
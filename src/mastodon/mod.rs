@@ -4,95 +4,307 @@
  * SPDX-License-Identifier: MIT
  */
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use async_trait::async_trait;
+use log::{debug, warn};
 use megalodon::megalodon::{PostStatusInputOptions, PostStatusOutput, UploadMediaInputOptions};
 use megalodon::{entities, error, generator, Megalodon};
 
 use crate::config::DailyScryConfig;
-use crate::error::{Error, Result};
-use crate::util::{split_text, Additional};
+use crate::error::{is_unauthorized, Error, Result};
+use crate::format::{
+    combine_face_texts, compose_post, compose_thread, LinkCost, PostBudget, PostOptions,
+    TEASER_DESCRIPTION, TEASER_TEXT,
+};
+use crate::post_target::{PostTarget, RenderedPost};
 
-pub async fn post(
-    config: &DailyScryConfig,
-    card_texts: Vec<String>,
-    artist: Option<String>,
-    images: Vec<PathBuf>,
-    link: &str,
-) -> Result<Vec<PostStatusOutput>> {
+/// Mana symbols become shortcodes with this prefix, e.g. `{W/U}` becomes `:mana_wu:`, unless
+/// `DAILY_SCRY_MANA_EMOJI_PATH` maps them to others
+const MANA_EMOJI_PREFIX: &str = "mana_";
+
+/// Mastodon only renders shortcodes that are not directly followed by another one or a `:`
+const SHORTCODE_SEPARATOR: char = '\u{200B}';
+
+/// Links in mastodon always take up 23 characters
+/// See: https://docs.joinmastodon.org/user/posting/#links
+const MASTODON_LINK_LENGTH: usize = 23;
+
+/// Mastodon rejects media descriptions longer than this
+const MASTODON_MEDIA_DESCRIPTION_LIMIT: usize = 1500;
+
+/// The language of a thread, unless the instance stores another one for its first status
+const MASTODON_DEFAULT_LANGUAGE: &str = "en";
+
+/// Mastodon allows at most this many images per post
+const MASTODON_MEDIA_LIMIT: usize = 4;
+
+/// The description of images without a card text, like the card back
+const CARD_BACK_DESCRIPTION: &str = "The back of a Magic: The Gathering card";
+
+pub struct MastodonTarget<'a> {
+    config: &'a DailyScryConfig,
+}
+
+impl<'a> MastodonTarget<'a> {
+    pub fn new(config: &'a DailyScryConfig) -> Result<Self> {
+        config.check_mastodon_config()?;
+        Ok(MastodonTarget { config })
+    }
+}
+
+#[async_trait(?Send)]
+impl PostTarget for MastodonTarget<'_> {
+    fn name(&self) -> &'static str {
+        "mastodon"
+    }
+
+    fn budget(&self) -> PostBudget {
+        budget(self.config)
+    }
+
+    async fn post(&self, post: &RenderedPost) -> Result<()> {
+        let outputs = self::post(self.config, post).await?;
+
+        for output in outputs {
+            match output {
+                PostStatusOutput::Status(status) => println!("Posted: {}", status.url.unwrap()),
+                PostStatusOutput::ScheduledStatus(scheduled_status) => {
+                    println!("Will post at {}", scheduled_status.scheduled_at)
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub async fn post(config: &DailyScryConfig, post: &RenderedPost) -> Result<Vec<PostStatusOutput>> {
     let client = create_client(config).await?;
+    let RenderedPost {
+        name,
+        card_texts,
+        artist,
+        price,
+        notes,
+        provenance,
+        images,
+        text_images,
+        token_images,
+        teaser,
+        link,
+        sensitive,
+        extra_hashtags,
+        weekday_hashtags,
+        oracle_id,
+        header,
+    } = post.clone();
+
+    let text = if config.combine_face_text {
+        combine_face_texts(card_texts.clone(), &config.face_separator).join("")
+    } else {
+        card_texts
+            .iter()
+            .filter(|text| !text.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let text = with_mana_symbols(config, text)?;
+    let options = PostOptions {
+        price,
+        notes,
+        links: vec![link.to_owned()],
+        hashtags: Some(
+            [
+                vec!["#MagicTheGathering".to_owned(), "#DailyScry".to_owned()],
+                extra_hashtags,
+                weekday_hashtags,
+            ]
+            .concat()
+            .join(" "),
+        ),
+        oracle_id,
+        header,
+    };
 
-    let text = card_texts.join("\n");
-    let hashtags = "\n#MagicTheGathering #DailyScry".to_owned();
-    let artist = format!("\n{}", artist.unwrap_or_default());
-
-    let splitted_texts = split_text(
-        text,
-        config.mastodon_character_limit.unwrap(),
-        vec![
-            Additional::Text(hashtags.clone()),
-            Additional::Text(artist.clone()),
-            Additional::Number(23), // This is for the link as links in mastodon always take up 23 characters See: https://docs.joinmastodon.org/user/posting/#links
-        ],
+    let (card_status, replies) = reveal_statuses(
+        config.reveal_delay_secs.map(|_| name.as_str()),
+        compose_thread(&text, &artist, &options, budget(config)),
+        &options,
     );
 
-    let images_and_texts = images.iter().zip(card_texts.iter());
+    let media = card_media(images, &card_texts, &provenance, text_images, token_images);
 
-    let media_ids_futures = images_and_texts
-        .map(|(image, card_text)| upload_media_file(&client, &image, card_text.to_string()));
+    let mut stages = media_stages(teaser, media);
+    let card_media = stages.pop().unwrap_or_default();
 
-    let media_ids = futures::future::join_all(media_ids_futures)
-        .await
-        .into_iter()
-        .collect::<std::result::Result<Vec<_>, megalodon::error::Error>>()?;
+    let mut thread = Thread::new(config, sensitive);
+    let mut results = vec![];
 
-    let status = format!("{}{}{}{}", splitted_texts[0], artist, link, hashtags);
+    for teaser_media in stages {
+        let media_ids = upload_media_files(&client, &teaser_media).await?;
+        let teaser_status = compose_post(TEASER_TEXT, &None, &teaser_options(&options));
+        let teaser_result = post_status(&client, &teaser_status, thread.options(Some(media_ids)))
+            .await
+            .map_err(Error::from)?;
+        thread.push(&teaser_result);
+        results.push(teaser_result);
+    }
 
-    let result = post_status(&client, &status, Some(media_ids), None)
-        .await
-        .map_err(|error| Error::MegalodonError { error: error })?;
+    let media_ids = upload_media_files(&client, &card_media).await?;
 
-    let mut reply_id = match result.clone() {
-        PostStatusOutput::Status(status) => status.id,
-        PostStatusOutput::ScheduledStatus(_) => "".to_owned(),
-    };
+    let result = post_status(&client, &card_status, thread.options(Some(media_ids)))
+        .await
+        .map_err(Error::from)?;
+    thread.push(&result);
+    results.push(result);
 
-    let mut results = vec![result];
+    if let Some(reveal_delay_secs) = config.reveal_delay_secs {
+        debug!("revealing the card text in {} seconds…", reveal_delay_secs);
+        tokio::time::sleep(Duration::from_secs(reveal_delay_secs as u64)).await;
+    }
 
-    for splitted_text in splitted_texts.into_iter().skip(1) {
-        let additional_status = format!("{}{}{}{}", splitted_text, artist, link, hashtags);
-        let additional_result = post_status(&client, &additional_status, None, Some(reply_id))
+    for additional_status in replies {
+        let additional_result = post_status(&client, &additional_status, thread.options(None))
             .await
-            .map_err(|error| Error::MegalodonError { error: error })?;
-        reply_id = match additional_result.clone() {
-            PostStatusOutput::Status(status) => status.id,
-            PostStatusOutput::ScheduledStatus(_) => "".to_owned(),
-        };
+            .map_err(Error::from)?;
+        thread.push(&additional_result);
         results.push(additional_result);
     }
 
     return Ok(results);
 }
 
+/// The [`PostBudget`] of a mastodon thread
+pub fn budget(config: &DailyScryConfig) -> PostBudget {
+    PostBudget {
+        character_limit: config.mastodon_character_limit.unwrap(),
+        link_cost: LinkCost::Fixed(MASTODON_LINK_LENGTH),
+        max_posts: config.max_thread_posts,
+        numbering: config.thread_numbering,
+    }
+}
+
+/// Returns the images of the card status and their descriptions
+///
+/// Images without a card text of their own, like the card back after the faces, get a generic
+/// description. Mastodon allows at most [`MASTODON_MEDIA_LIMIT`] images, the rest is left out.
+fn card_media(
+    images: Vec<PathBuf>,
+    card_texts: &[String],
+    provenance: &Option<String>,
+    text_images: Vec<(PathBuf, String)>,
+    token_images: Vec<(PathBuf, String)>,
+) -> Vec<(PathBuf, String)> {
+    let mut media = images
+        .iter()
+        .zip(card_texts.iter())
+        .map(|(image, card_text)| (image.clone(), alt_text_for(card_text, provenance)))
+        .chain(
+            images
+                .iter()
+                .skip(card_texts.len())
+                .map(|image| (image.clone(), CARD_BACK_DESCRIPTION.to_owned())),
+        )
+        .chain(
+            text_images
+                .into_iter()
+                .map(|(image, card_text)| (image, alt_text_for(&card_text, &None))),
+        )
+        .chain(token_images)
+        .collect::<Vec<_>>();
+    if media.len() > MASTODON_MEDIA_LIMIT {
+        warn!(
+            "mastodon allows only {} images per post, leaving out {} of them",
+            MASTODON_MEDIA_LIMIT,
+            media.len() - MASTODON_MEDIA_LIMIT
+        );
+        media.truncate(MASTODON_MEDIA_LIMIT);
+    }
+    media
+}
+
+/// Returns the media of every status in posting order
+///
+/// The teaser gets a status of its own, which the status of the card replies to.
+fn media_stages(
+    teaser: Option<PathBuf>,
+    media: Vec<(PathBuf, String)>,
+) -> Vec<Vec<(PathBuf, String)>> {
+    teaser
+        .map(|teaser| vec![(teaser, TEASER_DESCRIPTION.to_owned())])
+        .into_iter()
+        .chain([media])
+        .collect()
+}
+
+/// Returns the status that carries the card images and the statuses replying to it
+///
+/// If the card is revealed later, the images are posted with just the name and every status of
+/// the thread becomes a reply.
+///
+/// # Arguments
+///
+/// * `reveal_name` - The name of the card, if its text is revealed later
+/// * `statuses` - The statuses as returned by [`compose_thread`]
+/// * `options` - The [`PostOptions`] of the thread
+fn reveal_statuses(
+    reveal_name: Option<&str>,
+    mut statuses: Vec<String>,
+    options: &PostOptions,
+) -> (String, Vec<String>) {
+    match reveal_name {
+        Some(name) => (
+            compose_post(name, &None, &teaser_options(options)),
+            statuses,
+        ),
+        None => {
+            let replies = statuses.split_off(1);
+            (statuses.remove(0), replies)
+        }
+    }
+}
+
+/// The teaser only gets the hashtags, the price, notes and link would give the card away
+fn teaser_options(options: &PostOptions) -> PostOptions {
+    PostOptions {
+        hashtags: options.hashtags.clone(),
+        ..Default::default()
+    }
+}
+
+fn status_id(output: &PostStatusOutput) -> String {
+    match output {
+        PostStatusOutput::Status(status) => status.id.clone(),
+        PostStatusOutput::ScheduledStatus(_) => "".to_owned(),
+    }
+}
+
 async fn create_client(config: &DailyScryConfig) -> Result<Box<dyn Megalodon + Send + Sync>> {
     let client = generator(
         megalodon::SNS::Mastodon,
         config.mastodon_url.clone().unwrap().clone(),
         Some(config.mastodon_access_token.clone().unwrap().clone()),
-        Some("DailyScry".to_string()),
+        client_name(config),
     );
 
     let res = client.verify_account_credentials().await;
 
     if res.is_err() {
-        return Err(Error::MegalodonError {
-            error: res.expect_err("Should not happen"),
-        });
+        return Err(Error::from(res.expect_err("Should not happen")));
     }
 
     Ok(client)
 }
 
+/// The name of the client that is passed to [`megalodon::generator`]
+fn client_name(config: &DailyScryConfig) -> Option<String> {
+    Some(config.client_name.clone())
+}
+
 async fn wait_until_uploaded(
     client: &Box<dyn megalodon::Megalodon + Send + Sync>,
     id: &str,
@@ -112,13 +324,27 @@ async fn wait_until_uploaded(
     }
 }
 
+async fn upload_media_files(
+    client: &Box<dyn megalodon::Megalodon + Send + Sync>,
+    media: &[(PathBuf, String)],
+) -> Result<Vec<String>> {
+    let media_ids_futures = media
+        .iter()
+        .map(|(image, description)| upload_media_file(client, image, description.to_string()));
+
+    futures::future::join_all(media_ids_futures)
+        .await
+        .into_iter()
+        .collect()
+}
+
 async fn upload_media_file(
     client: &Box<dyn megalodon::Megalodon + Send + Sync>,
     file_path: &PathBuf,
     description: String,
-) -> std::result::Result<String, megalodon::error::Error> {
+) -> Result<String> {
     let options = UploadMediaInputOptions {
-        description: Some(description),
+        description: Some(description).filter(|description| !description.is_empty()),
         focus: None,
     };
     let res = client
@@ -126,7 +352,8 @@ async fn upload_media_file(
             file_path.clone().into_os_string().into_string().unwrap(),
             Some(&options),
         )
-        .await?;
+        .await
+        .map_err(|error| upload_failed(file_path, error))?;
 
     let uploaded_media = res.json();
 
@@ -137,7 +364,7 @@ async fn upload_media_file(
             match wait_until_uploaded(&client, &m.id).await {
                 Ok(attachment) => media = attachment,
                 Err(err) => {
-                    return Err(err);
+                    return Err(upload_failed(file_path, err));
                 }
             }
         }
@@ -149,24 +376,495 @@ async fn upload_media_file(
     return Ok(media.id);
 }
 
+/// Replaces the mana symbols of a status with custom emoji of the instance, if
+/// `DAILY_SCRY_MANA_SYMBOLS` is `custom_emoji`
+fn with_mana_symbols(config: &DailyScryConfig, text: String) -> Result<String> {
+    if config.mana_symbols.as_deref() != Some("custom_emoji") {
+        return Ok(text);
+    }
+    let mana_emoji = match &config.mana_emoji_path {
+        Some(path) => fs::read_to_string(path)
+            .map(|content| parse_mana_emoji(&content))
+            .map_err(|_| Error::ReadConfiguration {
+                key: "DAILY_SCRY_MANA_EMOJI_PATH".to_string(),
+            })?,
+        None => HashMap::new(),
+    };
+    Ok(custom_emoji_mana(&text, &mana_emoji))
+}
+
+/// Parses lines like `{W}=:mana_w:`, empty lines and lines starting with `#` are skipped
+fn parse_mana_emoji(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(symbol, shortcode)| (symbol.trim().to_uppercase(), shortcode.trim().to_owned()))
+        .collect()
+}
+
+/// Replaces every mana symbol like `{G}` with its shortcode in `mana_emoji`, or the default one
+fn custom_emoji_mana(text: &str, mana_emoji: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(length) => start + length + 1,
+            None => break,
+        };
+        let symbol = &rest[start..end];
+        result.push_str(&rest[..start]);
+        if is_mana_symbol(symbol) {
+            match mana_emoji.get(&symbol.to_uppercase()) {
+                Some(shortcode) => result.push_str(shortcode),
+                None => result.push_str(&default_mana_emoji(symbol)),
+            }
+            result.push(SHORTCODE_SEPARATOR);
+        } else {
+            result.push_str(symbol);
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Whether `symbol` looks like a mana or tap symbol, e.g. `{2}`, `{W/P}` or `{T}`
+fn is_mana_symbol(symbol: &str) -> bool {
+    let inner = symbol.trim_start_matches('{').trim_end_matches('}');
+    !inner.is_empty()
+        && inner.len() <= 5
+        && inner.chars().all(|c| c.is_ascii_alphanumeric() || c == '/')
+}
+
+/// The shortcode of a mana symbol, if `DAILY_SCRY_MANA_EMOJI_PATH` doesn't map it
+fn default_mana_emoji(symbol: &str) -> String {
+    let name = symbol
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    format!(":{}{}:", MANA_EMOJI_PREFIX, name)
+}
+
+/// Returns the description of an image, cut to fit [`MASTODON_MEDIA_DESCRIPTION_LIMIT`]
+///
+/// The provenance is kept, if the card text has to be cut. Images without a card text get no
+/// description.
+fn alt_text_for(card_text: &str, provenance: &Option<String>) -> String {
+    if card_text.is_empty() {
+        return String::new();
+    }
+    let suffix = provenance
+        .as_ref()
+        .map(|provenance| format!("\n\n{}", provenance))
+        .unwrap_or_default();
+    let text_limit = MASTODON_MEDIA_DESCRIPTION_LIMIT.saturating_sub(suffix.chars().count());
+    let text: String = card_text.chars().take(text_limit).collect();
+    format!("{}{}", text, suffix)
+}
+
+/// Wraps an error of uploading `file_path`, unless the access token is invalid
+///
+/// With an expired token the first request of a post is the upload, so it has to be reported
+/// like a rejected status.
+fn upload_failed(file_path: &Path, error: megalodon::error::Error) -> Error {
+    if is_unauthorized(&error) {
+        return Error::MastodonTokenInvalid;
+    }
+    Error::ImageUploadFailed {
+        file_name: file_path.display().to_string(),
+        source: error,
+    }
+}
+
+/// The statuses of a thread posted so far, which the next status replies to
+struct Thread<'a> {
+    config: &'a DailyScryConfig,
+    sensitive: bool,
+    reply_id: Option<String>,
+    language: String,
+}
+
+impl<'a> Thread<'a> {
+    fn new(config: &'a DailyScryConfig, sensitive: bool) -> Self {
+        Thread {
+            config,
+            sensitive,
+            reply_id: None,
+            language: MASTODON_DEFAULT_LANGUAGE.to_owned(),
+        }
+    }
+
+    /// The options of the next status, in the language of the thread
+    fn options(&self, media_ids: Option<Vec<String>>) -> PostStatusInputOptions {
+        status_options(
+            self.config,
+            self.sensitive,
+            media_ids,
+            self.reply_id.clone(),
+            &self.language,
+        )
+    }
+
+    /// Continues the thread with a reply to the posted `output`
+    fn push(&mut self, output: &PostStatusOutput) {
+        let language = match output {
+            PostStatusOutput::Status(status) => status.language.clone(),
+            PostStatusOutput::ScheduledStatus(_) => None,
+        };
+        self.reply(status_id(output), language);
+    }
+
+    /// Continues the thread with a reply to the status `id`
+    ///
+    /// The language the instance stored for the first status is reused by all of its replies.
+    fn reply(&mut self, id: String, language: Option<String>) {
+        if self.reply_id.is_none() {
+            if let Some(language) = language {
+                self.language = language;
+            }
+        }
+        self.reply_id = Some(id);
+    }
+}
+
+fn status_options(
+    config: &DailyScryConfig,
+    sensitive: bool,
+    media_ids: Option<Vec<String>>,
+    in_reply_to_id: Option<String>,
+    language: &str,
+) -> PostStatusInputOptions {
+    PostStatusInputOptions {
+        media_ids,
+        sensitive: Some(sensitive),
+        spoiler_text: if sensitive {
+            Some(config.mastodon_spoiler_text.clone())
+        } else {
+            None
+        },
+        visibility: Some(match in_reply_to_id {
+            Some(_) => reply_visibility(config),
+            None => entities::StatusVisibility::Public,
+        }),
+        in_reply_to_id,
+        language: Some(language.to_owned()),
+        ..Default::default()
+    }
+}
+
+/// The visibility of replies, which defaults to the public visibility of the first post
+fn reply_visibility(config: &DailyScryConfig) -> entities::StatusVisibility {
+    match config.mastodon_reply_visibility.as_deref() {
+        Some("unlisted") => entities::StatusVisibility::Unlisted,
+        Some("private") => entities::StatusVisibility::Private,
+        Some("direct") => entities::StatusVisibility::Direct,
+        _ => entities::StatusVisibility::Public,
+    }
+}
+
 async fn post_status(
     client: &Box<dyn megalodon::Megalodon + Send + Sync>,
     status: &str,
-    media_ids: Option<Vec<String>>,
-    in_reply_to_id: Option<String>,
+    options: PostStatusInputOptions,
 ) -> std::result::Result<megalodon::megalodon::PostStatusOutput, megalodon::error::Error> {
     let res = client
-        .post_status(
-            status.to_string(),
-            Some(&PostStatusInputOptions {
-                media_ids: media_ids,
-                sensitive: Some(false),
-                visibility: Some(entities::StatusVisibility::Public),
-                in_reply_to_id: in_reply_to_id,
-                language: Some("en".to_string()),
-                ..Default::default()
-            }),
-        )
+        .post_status(status.to_string(), Some(&options))
         .await?;
     Ok(res.json())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_emoji_mana() {
+        let mana_emoji = HashMap::new();
+        assert_eq!(
+            custom_emoji_mana("Grizzly Bears\t{1}{G}", &mana_emoji),
+            "Grizzly Bears\t:mana_1:\u{200B}:mana_g:\u{200B}"
+        );
+        assert_eq!(
+            custom_emoji_mana("{T}: Add {W/U}. {artist}", &mana_emoji),
+            ":mana_t:\u{200B}: Add :mana_wu:\u{200B}. {artist}"
+        );
+
+        let mana_emoji = parse_mana_emoji("# pips of our instance\n{g} = :forest_mana:\n\n");
+        assert_eq!(
+            custom_emoji_mana("{1}{G}", &mana_emoji),
+            ":mana_1:\u{200B}:forest_mana:\u{200B}"
+        );
+    }
+
+    #[test]
+    fn test_with_mana_symbols() {
+        temp_env::with_vars(
+            [
+                ("DAILY_SCRY_MANA_SYMBOLS", None::<&str>),
+                ("DAILY_SCRY_MANA_EMOJI_PATH", None),
+            ],
+            || {
+                let mut config = DailyScryConfig::new();
+                assert_eq!(
+                    with_mana_symbols(&config, "{1}{G}".to_owned()).unwrap(),
+                    "{1}{G}"
+                );
+
+                config.mana_symbols = Some("custom_emoji".to_owned());
+                assert_eq!(
+                    with_mana_symbols(&config, "{1}{G}".to_owned()).unwrap(),
+                    ":mana_1:\u{200B}:mana_g:\u{200B}"
+                );
+
+                config.mana_emoji_path = Some("/does/not/exist".to_owned());
+                assert!(with_mana_symbols(&config, "{1}{G}".to_owned()).is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn test_upload_failed_contains_file_name() {
+        let file_path = PathBuf::from("/tmp/face_1.png");
+        let source = megalodon::error::Error::ParseError(url::Url::parse("").unwrap_err());
+
+        let error = upload_failed(&file_path, source);
+
+        assert!(matches!(error, Error::ImageUploadFailed { .. }));
+        assert!(error.to_string().contains("/tmp/face_1.png"));
+    }
+
+    #[test]
+    fn test_upload_failed_unauthorized() {
+        let file_path = PathBuf::from("/tmp/face_1.png");
+        let source = megalodon::error::Error::new_own(
+            "The access token is invalid".to_owned(),
+            megalodon::error::Kind::HTTPStatusError,
+            None,
+            Some(401),
+        );
+
+        let error = upload_failed(&file_path, source);
+
+        assert!(matches!(error, Error::MastodonTokenInvalid));
+    }
+
+    #[test]
+    fn test_card_media_card_back() {
+        let images = vec![
+            PathBuf::from("/tmp/face_0.png"),
+            PathBuf::from("/tmp/face_1.png"),
+            PathBuf::from("/tmp/card_back_large.jpg"),
+        ];
+        let card_texts = vec![
+            "Delver of Secrets".to_owned(),
+            "Insectile Aberration".to_owned(),
+        ];
+
+        let media = card_media(images, &card_texts, &None, vec![], vec![]);
+
+        assert_eq!(
+            media,
+            vec![
+                (
+                    PathBuf::from("/tmp/face_0.png"),
+                    "Delver of Secrets".to_owned()
+                ),
+                (
+                    PathBuf::from("/tmp/face_1.png"),
+                    "Insectile Aberration".to_owned()
+                ),
+                (
+                    PathBuf::from("/tmp/card_back_large.jpg"),
+                    CARD_BACK_DESCRIPTION.to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_card_media_limit() {
+        let images = vec![PathBuf::from("/tmp/face_0.png")];
+        let card_texts = vec!["Grizzly Bears".to_owned()];
+        let token_images = (0..4)
+            .map(|index| {
+                (
+                    PathBuf::from(format!("/tmp/token_{}.png", index)),
+                    "Bear".to_owned(),
+                )
+            })
+            .collect();
+
+        let media = card_media(images, &card_texts, &None, vec![], token_images);
+
+        assert_eq!(media.len(), MASTODON_MEDIA_LIMIT);
+        assert_eq!(media[0].0, PathBuf::from("/tmp/face_0.png"));
+    }
+
+    #[test]
+    fn test_media_stages_teaser() {
+        let media = vec![(PathBuf::from("/tmp/test.png"), "Grizzly Bears".to_owned())];
+
+        let stages = media_stages(Some(PathBuf::from("/tmp/teaser.jpg")), media.clone());
+
+        assert_eq!(
+            stages,
+            vec![
+                vec![(
+                    PathBuf::from("/tmp/teaser.jpg"),
+                    TEASER_DESCRIPTION.to_owned()
+                )],
+                media
+            ]
+        );
+    }
+
+    #[test]
+    fn test_media_stages_without_teaser() {
+        let media = vec![(PathBuf::from("/tmp/test.png"), "Grizzly Bears".to_owned())];
+
+        assert_eq!(media_stages(None, media.clone()), vec![media]);
+    }
+
+    #[test]
+    fn test_alt_text_for() {
+        let card_text = "Black Lotus\t{0}\nArtifact\n{T}, Sacrifice Black Lotus: Add three mana of any one color.";
+        assert_eq!(alt_text_for(card_text, &None), card_text);
+        assert_eq!(
+            alt_text_for(
+                card_text,
+                &Some("from Limited Edition Alpha #232".to_owned())
+            ),
+            format!("{}\n\nfrom Limited Edition Alpha #232", card_text)
+        );
+        assert_eq!(
+            alt_text_for("", &Some("from Limited Edition Alpha #232".to_owned())),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_alt_text_for_respects_limit() {
+        let card_text = "a".repeat(2000);
+        let provenance = Some("from Limited Edition Alpha #232".to_owned());
+
+        let alt_text = alt_text_for(&card_text, &provenance);
+
+        assert_eq!(alt_text.chars().count(), MASTODON_MEDIA_DESCRIPTION_LIMIT);
+        assert!(alt_text.ends_with("\n\nfrom Limited Edition Alpha #232"));
+        assert_eq!(
+            alt_text_for(&card_text, &None).chars().count(),
+            MASTODON_MEDIA_DESCRIPTION_LIMIT
+        );
+    }
+
+    #[test]
+    fn test_client_name() {
+        temp_env::with_vars([("DAILY_SCRY_CLIENT_NAME", None::<&str>)], || {
+            let config = DailyScryConfig::new();
+            assert_eq!(client_name(&config).as_deref(), Some("DailyScry"));
+        });
+        temp_env::with_vars([("DAILY_SCRY_CLIENT_NAME", Some("DailyForkScry"))], || {
+            let config = DailyScryConfig::new();
+            assert_eq!(client_name(&config).as_deref(), Some("DailyForkScry"));
+        });
+    }
+
+    #[test]
+    fn test_reveal_statuses() {
+        let options = PostOptions {
+            links: vec!["https://scryfall.com/card/lea/155".to_owned()],
+            hashtags: Some("#MagicTheGathering #DailyScry".to_owned()),
+            ..Default::default()
+        };
+        let statuses = vec!["Grizzly Bears\n2/2".to_owned(), "2/2".to_owned()];
+
+        assert_eq!(
+            reveal_statuses(None, statuses.clone(), &options),
+            ("Grizzly Bears\n2/2".to_owned(), vec!["2/2".to_owned()])
+        );
+        assert_eq!(
+            reveal_statuses(Some("Grizzly Bears"), statuses.clone(), &options),
+            (
+                "Grizzly Bears\n#MagicTheGathering #DailyScry".to_owned(),
+                statuses
+            )
+        );
+    }
+
+    #[test]
+    fn test_thread_shares_language() {
+        let config = DailyScryConfig::default();
+        let mut thread = Thread::new(&config, false);
+
+        let first = thread.options(Some(vec!["media".to_owned()]));
+        thread.reply("1".to_owned(), Some("de".to_owned()));
+        let second = thread.options(None);
+        thread.reply("2".to_owned(), Some("fr".to_owned()));
+        let third = thread.options(None);
+        thread.reply("3".to_owned(), None);
+        let fourth = thread.options(None);
+
+        assert_eq!(first.language, Some(MASTODON_DEFAULT_LANGUAGE.to_owned()));
+        assert_eq!(first.in_reply_to_id, None);
+        for (options, reply_id) in [(second, "1"), (third, "2"), (fourth, "3")] {
+            assert_eq!(options.language, Some("de".to_owned()));
+            assert_eq!(options.in_reply_to_id, Some(reply_id.to_owned()));
+        }
+    }
+
+    #[test]
+    fn test_reply_visibility() {
+        let mut config = DailyScryConfig::default();
+
+        let options = status_options(
+            &config,
+            false,
+            None,
+            Some("1".to_owned()),
+            MASTODON_DEFAULT_LANGUAGE,
+        );
+        assert_eq!(options.visibility, Some(entities::StatusVisibility::Public));
+
+        config.mastodon_reply_visibility = Some("unlisted".to_owned());
+        let options = status_options(&config, false, None, None, MASTODON_DEFAULT_LANGUAGE);
+        assert_eq!(options.visibility, Some(entities::StatusVisibility::Public));
+        let options = status_options(
+            &config,
+            false,
+            None,
+            Some("1".to_owned()),
+            MASTODON_DEFAULT_LANGUAGE,
+        );
+        assert_eq!(
+            options.visibility,
+            Some(entities::StatusVisibility::Unlisted)
+        );
+    }
+
+    #[test]
+    fn test_spoiler_text_only_when_sensitive() {
+        temp_env::with_vars(
+            [(
+                "DAILY_SCRY_MASTODON_SPOILER_TEXT",
+                Some("Card with content warning"),
+            )],
+            || {
+                let config = DailyScryConfig::new();
+
+                let options = status_options(&config, true, None, None, MASTODON_DEFAULT_LANGUAGE);
+                assert_eq!(options.sensitive, Some(true));
+                assert_eq!(
+                    options.spoiler_text.as_deref(),
+                    Some("Card with content warning")
+                );
+
+                let options = status_options(&config, false, None, None, MASTODON_DEFAULT_LANGUAGE);
+                assert_eq!(options.sensitive, Some(false));
+                assert_eq!(options.spoiler_text, None);
+            },
+        );
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * SPDX-FileCopyrightText: 2024 Philip Molares <philip.molares@udo.edu>
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use log::debug;
+
+use crate::error::Result;
+use crate::format::PostBudget;
+
+/// Everything a [`PostTarget`] needs to post a card
+#[derive(Clone, Debug, Default)]
+pub struct RenderedPost {
+    /// The name of the card, posted before the text if it is revealed later
+    pub name: String,
+    /// The formatted texts, one per image
+    pub card_texts: Vec<String>,
+    /// The artist as returned by [`crate::format::get_artist`]
+    pub artist: Option<String>,
+    /// A price line as returned by [`crate::format::get_price`]
+    pub price: Option<String>,
+    /// Notes as returned by [`crate::format::get_notes`]
+    pub notes: Vec<String>,
+    /// The set and collector number as returned by [`crate::format::get_provenance`], added to
+    /// the image descriptions
+    pub provenance: Option<String>,
+    /// The downloaded images
+    pub images: Vec<PathBuf>,
+    /// The card texts rendered to images and their descriptions
+    pub text_images: Vec<(PathBuf, String)>,
+    /// The images of the tokens the card creates and their descriptions
+    pub token_images: Vec<(PathBuf, String)>,
+    /// The art crop that is posted before the card, if teasers are enabled
+    pub teaser: Option<PathBuf>,
+    /// The link to the card on scryfall
+    pub link: String,
+    /// Whether the card has a content warning
+    pub sensitive: bool,
+    /// Hashtags added to the default ones
+    pub extra_hashtags: Vec<String>,
+    /// Hashtags of today's weekday, added on every platform
+    pub weekday_hashtags: Vec<String>,
+    /// The shortened oracle id as returned by [`crate::format::get_oracle_id`]
+    pub oracle_id: Option<String>,
+    /// The header of the first post as returned by [`crate::format::post_header`]
+    pub header: Option<String>,
+}
+
+#[async_trait(?Send)]
+pub trait PostTarget {
+    fn name(&self) -> &'static str;
+
+    /// The [`PostBudget`] the target splits the card texts with
+    fn budget(&self) -> PostBudget;
+
+    async fn post(&self, post: &RenderedPost) -> Result<()>;
+}
+
+/// Posts `post` to every target in order and stops at the first failing one
+///
+/// Every target composes its posts from the unsplit card texts with its own [`PostBudget`], so
+/// the limits of one platform never affect the posts of another.
+///
+/// # Arguments
+///
+/// * `targets` - The enabled [`PostTarget`]s
+/// * `post` - The [`RenderedPost`] to post
+pub async fn post_to_all(targets: &[Box<dyn PostTarget + '_>], post: &RenderedPost) -> Result<()> {
+    for target in targets {
+        debug!(
+            "creating {} post with a budget of {:?}…",
+            target.name(),
+            target.budget()
+        );
+        target.post(post).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::format::LinkCost;
+    use std::cell::RefCell;
+
+    struct MockTarget<'a> {
+        name: &'static str,
+        fail: bool,
+        posted: &'a RefCell<Vec<(&'static str, String)>>,
+    }
+
+    #[async_trait(?Send)]
+    impl PostTarget for MockTarget<'_> {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn budget(&self) -> PostBudget {
+            PostBudget {
+                character_limit: 500,
+                link_cost: LinkCost::Literal,
+                max_posts: None,
+                numbering: false,
+            }
+        }
+
+        async fn post(&self, post: &RenderedPost) -> Result<()> {
+            if self.fail {
+                return Err(Error::TextNotFound);
+            }
+            self.posted
+                .borrow_mut()
+                .push((self.name, post.card_texts.join("")));
+            Ok(())
+        }
+    }
+
+    fn rendered_post() -> RenderedPost {
+        RenderedPost {
+            card_texts: vec!["Grizzly Bears".to_owned()],
+            link: "https://scryfall.com".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_to_all() {
+        let posted = RefCell::new(vec![]);
+        let targets: Vec<Box<dyn PostTarget>> = vec![
+            Box::new(MockTarget {
+                name: "first",
+                fail: false,
+                posted: &posted,
+            }),
+            Box::new(MockTarget {
+                name: "second",
+                fail: false,
+                posted: &posted,
+            }),
+        ];
+
+        post_to_all(&targets, &rendered_post()).await.unwrap();
+        assert_eq!(
+            *posted.borrow(),
+            vec![
+                ("first", "Grizzly Bears".to_owned()),
+                ("second", "Grizzly Bears".to_owned())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_to_all_stops_at_failure() {
+        let posted = RefCell::new(vec![]);
+        let targets: Vec<Box<dyn PostTarget>> = vec![
+            Box::new(MockTarget {
+                name: "first",
+                fail: true,
+                posted: &posted,
+            }),
+            Box::new(MockTarget {
+                name: "second",
+                fail: false,
+                posted: &posted,
+            }),
+        ];
+
+        assert!(post_to_all(&targets, &rendered_post()).await.is_err());
+        assert!(posted.borrow().is_empty());
+    }
+}
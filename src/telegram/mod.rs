@@ -4,86 +4,752 @@
  * SPDX-License-Identifier: MIT
  */
 
-use std::path::PathBuf;
+use async_trait::async_trait;
+use log::warn;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::config::DailyScryConfig;
-use crate::error::Result;
-use crate::util::{split_text, Additional};
+use crate::error::{Error, Result};
+use crate::format::{
+    ability_word, combine_face_texts, compose_thread, LinkCost, PostBudget, PostOptions,
+    TEASER_TEXT,
+};
+use crate::post_target::{PostTarget, RenderedPost};
 
 use teloxide_core::{
-    payloads::{SendMessageSetters, SendPhotoSetters},
+    payloads::{
+        SendDocument, SendDocumentSetters, SendMessage, SendMessageSetters, SendPhoto,
+        SendPhotoSetters,
+    },
     prelude::Request,
-    requests::Requester,
-    types::{InputFile, ParseMode},
+    requests::{JsonRequest, MultipartRequest, Requester},
+    types::{InputFile, MessageId, ParseMode},
     Bot,
 };
 
-pub async fn post(
-    config: &DailyScryConfig,
-    card_texts: Vec<String>,
-    artist: Option<String>,
-    images: Vec<PathBuf>,
-    link: &str,
-) -> Result<()> {
+/// The caption of the images, if `DAILY_SCRY_TELEGRAM_CAPTION_TEMPLATE` is not set
+pub const DEFAULT_TELEGRAM_CAPTION_TEMPLATE: &str = "{link}";
+
+/// Telegram rejects captions longer than this
+/// See: https://core.telegram.org/bots/api#sendphoto
+const TELEGRAM_CAPTION_LIMIT: usize = 1024;
+
+/// How often the image of a face is sent, before the face is given up
+const IMAGE_SEND_ATTEMPTS: usize = 3;
+
+/// The time waited before an image is sent again
+const IMAGE_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+pub struct TelegramTarget<'a> {
+    config: &'a DailyScryConfig,
+}
+
+impl<'a> TelegramTarget<'a> {
+    pub fn new(config: &'a DailyScryConfig) -> Result<Self> {
+        config.check_telegram_config()?;
+        Ok(TelegramTarget { config })
+    }
+}
+
+#[async_trait(?Send)]
+impl PostTarget for TelegramTarget<'_> {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn budget(&self) -> PostBudget {
+        budget(self.config)
+    }
+
+    async fn post(&self, post: &RenderedPost) -> Result<()> {
+        self::post(self.config, post).await?;
+        println!(
+            "Posted to {}",
+            self.config.telegram_chat_id.clone().unwrap()
+        );
+        Ok(())
+    }
+}
+
+pub async fn post(config: &DailyScryConfig, post: &RenderedPost) -> Result<()> {
+    let RenderedPost {
+        name,
+        card_texts,
+        artist,
+        price,
+        notes,
+        provenance,
+        images,
+        text_images,
+        token_images,
+        teaser,
+        link,
+        weekday_hashtags,
+        oracle_id,
+        header,
+        ..
+    } = post.clone();
+    let card_texts = if config.combine_face_text {
+        combine_face_texts(card_texts, &config.face_separator)
+    } else {
+        card_texts
+    };
+    // the images of double-faced cards get a description, so screen readers can tell them apart
+    let describe_images = config.telegram_describe_image
+        && card_texts.iter().filter(|text| !text.is_empty()).count() > 1;
+    let card_texts = if describe_images {
+        card_texts
+            .iter()
+            .map(|card_text| with_image_description(card_text, &provenance))
+            .collect()
+    } else {
+        card_texts
+    };
     let images_and_texts = images.iter().zip(card_texts.iter());
+    let caption = caption(config, &name, &artist, &link);
+    let options = PostOptions {
+        oracle_id,
+        header,
+        ..message_options(config, price, notes, &link, weekday_hashtags)
+    };
+    // only the messages of the first face start with the header
+    let reply_options = PostOptions {
+        header: None,
+        ..options.clone()
+    };
 
-    let bot = Bot::new(&config.telegram_token.clone().unwrap());
+    let bot = create_bot(config)?;
     let chat_id = config.telegram_chat_id.clone().unwrap();
 
-    let futures = images_and_texts.map(|(image, card_text)| {
-        map_function(
-            &bot,
-            &chat_id,
-            artist.clone(),
-            &image,
-            card_text,
-            link,
-            config,
-        )
-    });
+    let reply_to = match &teaser {
+        Some(teaser) => Some(
+            photo_request(&bot, &chat_id, teaser, TEASER_TEXT, config, None)
+                .send()
+                .await?
+                .id,
+        ),
+        None => None,
+    };
 
-    futures::future::join_all(futures)
-        .await
-        .into_iter()
-        .collect::<Result<Vec<_>>>()?;
+    let futures = images_and_texts
+        .enumerate()
+        .map(|(index, (image, card_text))| {
+            map_function(
+                &bot,
+                &chat_id,
+                artist.clone(),
+                if index == 0 { &options } else { &reply_options },
+                &image,
+                card_text,
+                &caption,
+                reply_to,
+                config,
+            )
+        });
+
+    // a face that could not be sent doesn't keep the others from being posted
+    let results = futures::future::join_all(futures).await;
+    if let Some(note) = failed_faces_note(results)? {
+        send_message(&bot, &chat_id, note, config).await?;
+    }
+
+    let extra_images = images.iter().skip(card_texts.len()).cloned().chain(
+        text_images
+            .into_iter()
+            .chain(token_images)
+            .map(|(image, _)| image),
+    );
+    for extra_image in extra_images {
+        send_image(&bot, &chat_id, &extra_image, &caption, None, config).await?;
+    }
     Ok(())
 }
 
+/// The parts appended to every message, the link is only added if it goes in the message body
+fn message_options(
+    config: &DailyScryConfig,
+    price: Option<String>,
+    notes: Vec<String>,
+    link: &str,
+    weekday_hashtags: Vec<String>,
+) -> PostOptions {
+    let hashtags = [config.telegram_hashtags.clone(), weekday_hashtags]
+        .concat()
+        .join(" ");
+    PostOptions {
+        price,
+        notes,
+        links: if config.telegram_link_in_body {
+            vec![link.to_owned()]
+        } else {
+            vec![]
+        },
+        hashtags: Some(hashtags).filter(|hashtags| !hashtags.is_empty()),
+        ..Default::default()
+    }
+}
+
+fn create_bot(config: &DailyScryConfig) -> Result<Bot> {
+    let timeout = config.http_timeout();
+    let client = teloxide_core::net::default_reqwest_settings()
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .build()
+        .map_err(|error| teloxide_core::RequestError::Network(error))?;
+    Ok(Bot::with_client(
+        config.telegram_token.clone().unwrap(),
+        client,
+    ))
+}
+
 async fn map_function(
     bot: &Bot,
     chat_id: &String,
     artist: Option<String>,
+    options: &PostOptions,
     image_path: &PathBuf,
     text: &String,
-    link: &str,
+    caption: &str,
+    reply_to: Option<MessageId>,
     config: &DailyScryConfig,
 ) -> Result<()> {
-    send_image(bot, chat_id, image_path, link).await?;
-    let artist = artist.unwrap_or_default();
-    let splitted_texts = split_text(
-        text.to_string(),
-        config.telegram_character_limit.unwrap(),
-        vec![Additional::Text(artist.clone())],
-    );
-    for text in splitted_texts {
-        send_message(bot, chat_id, &artist, text).await?;
+    with_retries(IMAGE_SEND_ATTEMPTS, IMAGE_RETRY_DELAY, || {
+        send_image(bot, chat_id, image_path, caption, reply_to, config)
+    })
+    .await?;
+    if text.is_empty() {
+        return Ok(());
+    }
+    for message in messages(text, &artist, options, config) {
+        send_message(bot, chat_id, message, config).await?;
     }
     Ok(())
 }
 
-async fn send_image(bot: &Bot, chat_id: &String, image_path: &PathBuf, link: &str) -> Result<()> {
-    bot.send_photo(chat_id.clone(), InputFile::file(image_path))
-        .caption(link)
-        .send()
-        .await?;
+/// Runs `send` until it succeeds, transport errors are tried again up to `attempts` times
+async fn with_retries<F, Fut>(attempts: usize, delay: Duration, mut send: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut attempt = 1;
+    loop {
+        match send().await {
+            Err(error) if attempt < attempts && is_transport_error(&error) => {
+                warn!(
+                    "sending an image failed (attempt {} of {}): {}",
+                    attempt, attempts, error
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether `error` happened on the way to telegram, so sending again may work
+///
+/// Errors telegram answered with, like a blocked bot, are not.
+fn is_transport_error(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Timeout
+            | Error::TeloxideError {
+                error: teloxide_core::RequestError::Network(_) | teloxide_core::RequestError::Io(_),
+            }
+    )
+}
+
+/// Returns a note naming the faces that could not be sent, if the others were
+///
+/// Fails with the first error if no face could be sent at all.
+fn failed_faces_note(results: Vec<Result<()>>) -> Result<Option<String>> {
+    let face_count = results.len();
+    let mut failed_faces = vec![];
+    let mut first_error = None;
+    for (index, result) in results.into_iter().enumerate() {
+        if let Err(error) = result {
+            warn!("could not send face {} to telegram: {}", index + 1, error);
+            failed_faces.push((index + 1).to_string());
+            first_error.get_or_insert(error);
+        }
+    }
+    match first_error {
+        None => Ok(None),
+        Some(error) if failed_faces.len() == face_count => Err(error),
+        Some(_) => Ok(Some(format!(
+            "({} {} of {} could not be sent)",
+            if failed_faces.len() == 1 {
+                "face"
+            } else {
+                "faces"
+            },
+            failed_faces.join(", "),
+            face_count
+        ))),
+    }
+}
+
+/// Prepends a line describing the image to a card text, as telegram images carry no alt text
+///
+/// The description holds the name and type line of the face and the provenance, if it is set.
+/// Empty texts stay empty.
+fn with_image_description(card_text: &str, provenance: &Option<String>) -> String {
+    let mut lines = card_text.lines();
+    let name = match lines.next() {
+        Some(line) => line.split('\t').next().unwrap_or_default(),
+        None => return String::new(),
+    };
+    let description = [Some(name), lines.next(), provenance.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("Image: {}\n\n{}", description, card_text)
+}
+
+/// Renders `DAILY_SCRY_TELEGRAM_CAPTION_TEMPLATE` for the images of a card
+///
+/// `{name}`, `{link}` and `{artist}` are replaced, where `{artist}` is the artist line. Captions
+/// longer than telegram allows are truncated.
+fn caption(config: &DailyScryConfig, name: &str, artist: &Option<String>, link: &str) -> String {
+    let caption = config
+        .telegram_caption_template
+        .replace("{name}", name)
+        .replace("{link}", link)
+        .replace("{artist}", artist.as_deref().unwrap_or_default().trim());
+    if caption.chars().count() <= TELEGRAM_CAPTION_LIMIT {
+        return caption;
+    }
+    let truncated: String = caption.chars().take(TELEGRAM_CAPTION_LIMIT - 1).collect();
+    format!("{}…", truncated)
+}
+
+/// Splits a card text into messages, each ending with the artist, the [`PostOptions`] and the
+/// hashtags
+fn messages(
+    text: &str,
+    artist: &Option<String>,
+    options: &PostOptions,
+    config: &DailyScryConfig,
+) -> Vec<String> {
+    compose_thread(text, artist, options, budget(config))
+}
+
+/// The [`PostBudget`] of the messages of a card text, telegram counts links with their length
+pub fn budget(config: &DailyScryConfig) -> PostBudget {
+    PostBudget {
+        character_limit: config.telegram_character_limit.unwrap(),
+        link_cost: LinkCost::Literal,
+        max_posts: config.max_thread_posts,
+        numbering: config.thread_numbering,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ImageRequest {
+    Photo,
+    Document,
+}
+
+fn image_request(config: &DailyScryConfig) -> ImageRequest {
+    if config.telegram_send_as_document {
+        return ImageRequest::Document;
+    }
+    ImageRequest::Photo
+}
+
+async fn send_image(
+    bot: &Bot,
+    chat_id: &String,
+    image_path: &Path,
+    caption: &str,
+    reply_to: Option<MessageId>,
+    config: &DailyScryConfig,
+) -> Result<()> {
+    match image_request(config) {
+        ImageRequest::Photo => {
+            photo_request(bot, chat_id, image_path, caption, config, reply_to)
+                .send()
+                .await?;
+        }
+        ImageRequest::Document => {
+            document_request(bot, chat_id, image_path, caption, config, reply_to)
+                .send()
+                .await?;
+        }
+    }
     Ok(())
 }
 
-async fn send_message(bot: &Bot, chat_id: &String, artist: &String, text: String) -> Result<()> {
-    let text_with_artist = format!("{}{}", text, artist);
-    bot.send_message(chat_id.clone(), text_with_artist)
-        .parse_mode(ParseMode::Html)
+/// The topic of a supergroup the posts are sent to, if one is configured
+fn thread_id(config: &DailyScryConfig) -> Option<i32> {
+    config
+        .telegram_thread_id
+        .as_ref()
+        .and_then(|thread_id| thread_id.parse().ok())
+}
+
+fn photo_request(
+    bot: &Bot,
+    chat_id: &String,
+    image_path: &Path,
+    caption: &str,
+    config: &DailyScryConfig,
+    reply_to: Option<MessageId>,
+) -> MultipartRequest<SendPhoto> {
+    let request = bot
+        .send_photo(chat_id.clone(), InputFile::file(image_path))
+        .caption(caption);
+    let request = match thread_id(config) {
+        Some(thread_id) => request.message_thread_id(thread_id),
+        None => request,
+    };
+    match reply_to {
+        Some(reply_to) => request.reply_to_message_id(reply_to),
+        None => request,
+    }
+}
+
+fn document_request(
+    bot: &Bot,
+    chat_id: &String,
+    image_path: &Path,
+    caption: &str,
+    config: &DailyScryConfig,
+    reply_to: Option<MessageId>,
+) -> MultipartRequest<SendDocument> {
+    let request = bot
+        .send_document(chat_id.clone(), InputFile::file(image_path))
+        .caption(caption);
+    let request = match thread_id(config) {
+        Some(thread_id) => request.message_thread_id(thread_id),
+        None => request,
+    };
+    match reply_to {
+        Some(reply_to) => request.reply_to_message_id(reply_to),
+        None => request,
+    }
+}
+
+fn message_request(
+    bot: &Bot,
+    chat_id: &String,
+    text: String,
+    config: &DailyScryConfig,
+) -> JsonRequest<SendMessage> {
+    let request = bot
+        .send_message(chat_id.clone(), text)
+        .parse_mode(ParseMode::Html);
+    match thread_id(config) {
+        Some(thread_id) => request.message_thread_id(thread_id),
+        None => request,
+    }
+}
+
+/// Escapes the characters telegram treats as markup in messages sent with [`ParseMode::Html`]
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Puts the ability words at the start of lines in italics
+///
+/// The text must already be escaped with [`escape_html`].
+fn emphasize_ability_words(text: &str) -> String {
+    text.split('\n')
+        .map(|line| match ability_word(line) {
+            Some(ability_word) => format!("<i>{}</i>{}", ability_word, &line[ability_word.len()..]),
+            None => line.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+async fn send_message(
+    bot: &Bot,
+    chat_id: &String,
+    message: String,
+    config: &DailyScryConfig,
+) -> Result<()> {
+    let message = escape_html(&message);
+    let message = if config.emphasize_ability_words {
+        emphasize_ability_words(&message)
+    } else {
+        message
+    };
+    message_request(bot, chat_id, message, config)
         .send()
         .await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use teloxide_core::requests::HasPayload;
+
+    fn thread_config(thread_id: Option<&str>) -> DailyScryConfig {
+        temp_env::with_vars([("DAILY_SCRY_TELEGRAM_THREAD_ID", thread_id)], || {
+            DailyScryConfig::new()
+        })
+    }
+
+    #[test]
+    fn test_escape_html() {
+        let card_text =
+            "Fire // Ice\t{1}{R} & {1}{U}\nDeal 2 damage to a creature with power <3 > 0";
+        assert_eq!(
+            escape_html(card_text),
+            "Fire // Ice\t{1}{R} &amp; {1}{U}\nDeal 2 damage to a creature with power &lt;3 &gt; 0"
+        );
+        assert_eq!(escape_html("&lt;"), "&amp;lt;");
+    }
+
+    #[tokio::test]
+    async fn test_with_retries() {
+        let attempts = std::cell::Cell::new(0);
+        let result = with_retries(3, Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                match attempt {
+                    1 => Err(Error::Timeout),
+                    _ => Ok(()),
+                }
+            }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(attempts.get(), 2);
+
+        attempts.set(0);
+        let result = with_retries(3, Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async { Err(Error::ImageNotFound) }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::ImageNotFound)));
+        assert_eq!(attempts.get(), 1);
+
+        attempts.set(0);
+        let result = with_retries(3, Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async { Err(Error::Timeout) }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(attempts.get(), 3);
+
+        attempts.set(0);
+        let result = with_retries(3, Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async {
+                Err(Error::TeloxideError {
+                    error: teloxide_core::RequestError::Api(teloxide_core::ApiError::BotBlocked),
+                })
+            }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::TeloxideError { .. })));
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn test_failed_faces_note() {
+        assert_eq!(failed_faces_note(vec![Ok(()), Ok(())]).unwrap(), None);
+        assert_eq!(
+            failed_faces_note(vec![Ok(()), Err(Error::Timeout)]).unwrap(),
+            Some("(face 2 of 2 could not be sent)".to_owned())
+        );
+        assert_eq!(
+            failed_faces_note(vec![Err(Error::Timeout), Ok(()), Err(Error::Timeout)]).unwrap(),
+            Some("(faces 1, 3 of 3 could not be sent)".to_owned())
+        );
+        assert!(matches!(
+            failed_faces_note(vec![Err(Error::ImageNotFound), Err(Error::Timeout)]),
+            Err(Error::ImageNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_with_image_description() {
+        let config = DailyScryConfig::default();
+        let options = PostOptions::default();
+        let front = "Kytheon, Hero of Akros\t{W}\nLegendary Creature — Human Soldier\n2/1";
+        let provenance = Some("from Magic Origins #23".to_owned());
+
+        let text = with_image_description(front, &provenance);
+
+        assert_eq!(
+            messages(&text, &None, &options, &config)[0]
+                .lines()
+                .next()
+                .unwrap(),
+            "Image: Kytheon, Hero of Akros, Legendary Creature — Human Soldier, from Magic Origins #23"
+        );
+        assert!(text.ends_with(front));
+        assert_eq!(with_image_description("", &provenance), "");
+    }
+
+    #[test]
+    fn test_caption() {
+        let mut config = DailyScryConfig::default();
+        let artist = Some("\n\nIllustrated by Jeff A. Menges".to_owned());
+        let link = "https://scryfall.com/card/lea/155";
+
+        assert_eq!(caption(&config, "Grizzly Bears", &artist, link), link);
+
+        config.telegram_caption_template = "{name} – {artist}\n{link}".to_owned();
+        assert_eq!(
+            caption(&config, "Grizzly Bears", &artist, link),
+            "Grizzly Bears – Illustrated by Jeff A. Menges\nhttps://scryfall.com/card/lea/155"
+        );
+
+        config.telegram_caption_template = "{name}".repeat(100);
+        let caption = caption(&config, "Grizzly Bears", &artist, link);
+        assert_eq!(caption.chars().count(), TELEGRAM_CAPTION_LIMIT);
+        assert!(caption.ends_with('…'));
+    }
+
+    #[test]
+    fn test_messages_with_hashtags() {
+        let mut config = DailyScryConfig::default();
+        config.telegram_character_limit = Some(4096);
+        config.telegram_hashtags = vec!["#MagicTheGathering".to_owned(), "#DailyScry".to_owned()];
+        let link = "https://scryfall.com/card/lea/155";
+        let artist = Some("\n\nIllustrated by Jeff A. Menges".to_owned());
+
+        let options = message_options(&config, None, vec![], link, vec![]);
+        assert_eq!(
+            messages("Grizzly Bears", &artist, &options, &config),
+            vec!["Grizzly Bears\n\nIllustrated by Jeff A. Menges\n#MagicTheGathering #DailyScry"]
+        );
+
+        config.telegram_link_in_body = true;
+        let options = message_options(
+            &config,
+            None,
+            vec![],
+            link,
+            vec!["#ThrowbackThursday".to_owned()],
+        );
+        assert_eq!(
+            messages("Grizzly Bears", &artist, &options, &config),
+            vec!["Grizzly Bears\n\nIllustrated by Jeff A. Menges\n\nhttps://scryfall.com/card/lea/155\n#MagicTheGathering #DailyScry #ThrowbackThursday"]
+        );
+    }
+
+    #[test]
+    fn test_messages_budget_hashtags() {
+        let mut config = DailyScryConfig::default();
+        config.telegram_character_limit = Some(30);
+        config.telegram_hashtags = vec!["#DailyScry".to_owned()];
+        let options = message_options(&config, None, vec![], "", vec![]);
+
+        let messages = messages(&"a".repeat(40), &None, &options, &config);
+
+        assert!(messages.len() > 1);
+        assert!(messages
+            .iter()
+            .all(|message| message.chars().count() <= 30 && message.ends_with("\n#DailyScry")));
+    }
+
+    #[test]
+    fn test_emphasize_ability_words() {
+        let steppe_lynx = escape_html(
+            "Steppe Lynx\t{W}\nCreature — Cat\nLandfall — Whenever a land you control enters, Steppe Lynx gets +2/+2 until end of turn.\n0/1",
+        );
+        assert_eq!(
+            emphasize_ability_words(&steppe_lynx),
+            "Steppe Lynx\t{W}\nCreature — Cat\n<i>Landfall</i> — Whenever a land you control enters, Steppe Lynx gets +2/+2 until end of turn.\n0/1"
+        );
+    }
+
+    #[test]
+    fn test_requests_with_thread_id() {
+        let config = thread_config(Some("42"));
+        let bot = Bot::new("test_telegram_token");
+        let chat_id = "test_telegram_chat_id".to_owned();
+        let image_path = PathBuf::from("test.png");
+
+        let request = photo_request(&bot, &chat_id, &image_path, "link", &config, None);
+        assert_eq!(request.payload_ref().message_thread_id, Some(42));
+        let request = document_request(&bot, &chat_id, &image_path, "link", &config, None);
+        assert_eq!(request.payload_ref().message_thread_id, Some(42));
+        let request = message_request(&bot, &chat_id, "text".to_owned(), &config);
+        assert_eq!(request.payload_ref().message_thread_id, Some(42));
+    }
+
+    #[test]
+    fn test_requests_without_thread_id() {
+        let config = thread_config(None);
+        let bot = Bot::new("test_telegram_token");
+        let chat_id = "test_telegram_chat_id".to_owned();
+
+        let request = message_request(&bot, &chat_id, "text".to_owned(), &config);
+        assert_eq!(request.payload_ref().message_thread_id, None);
+    }
+
+    #[test]
+    fn test_requests_reply_to_teaser() {
+        let config = DailyScryConfig::default();
+        let bot = Bot::new("test_telegram_token");
+        let chat_id = "test_telegram_chat_id".to_owned();
+        let image_path = PathBuf::from("test.png");
+
+        let teaser_request = photo_request(&bot, &chat_id, &image_path, TEASER_TEXT, &config, None);
+        assert_eq!(teaser_request.payload_ref().reply_to_message_id, None);
+        let request = photo_request(
+            &bot,
+            &chat_id,
+            &image_path,
+            "link",
+            &config,
+            Some(MessageId(7)),
+        );
+        assert_eq!(
+            request.payload_ref().reply_to_message_id,
+            Some(MessageId(7))
+        );
+        let request = document_request(
+            &bot,
+            &chat_id,
+            &image_path,
+            "link",
+            &config,
+            Some(MessageId(7)),
+        );
+        assert_eq!(
+            request.payload_ref().reply_to_message_id,
+            Some(MessageId(7))
+        );
+    }
+
+    #[test]
+    fn test_image_request_photo() {
+        temp_env::with_vars(
+            [("DAILY_SCRY_TELEGRAM_SEND_AS_DOCUMENT", None::<&str>)],
+            || {
+                let config = DailyScryConfig::new();
+                assert_eq!(image_request(&config), ImageRequest::Photo);
+            },
+        );
+    }
+
+    #[test]
+    fn test_image_request_document() {
+        temp_env::with_vars(
+            [("DAILY_SCRY_TELEGRAM_SEND_AS_DOCUMENT", Some("true"))],
+            || {
+                let config = DailyScryConfig::new();
+                assert_eq!(image_request(&config), ImageRequest::Document);
+            },
+        );
+    }
+}
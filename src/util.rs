@@ -17,29 +17,53 @@ pub fn split_text(
     let character_already_used = additional_texts
         .into_iter()
         .map(|additional| match additional {
-            Additional::Text(text) => text.len(),
+            Additional::Text(text) => text.chars().count(),
             Additional::Number(number) => number,
         })
         .fold(0, |accumulator, number| accumulator + number);
-    let number_of_characters = character_limit - character_already_used;
+    let number_of_characters = character_limit.saturating_sub(character_already_used);
+    // every chunk but the last ends with "…", but always take at least one character so that
+    // splitting makes progress even if the limit is used up by the additional texts
+    let chunk_size = number_of_characters.saturating_sub(1).max(1);
 
     let mut texts = vec![];
-    let mut text_to_split = text.clone();
+    let mut text_to_split: Vec<char> = text.chars().collect();
     while text_to_split.len() > 0 {
-        if number_of_characters >= text_to_split.len() {
-            texts.push(text_to_split);
+        if number_of_characters.max(1) >= text_to_split.len() {
+            texts.push(text_to_split.iter().collect());
             break;
         }
+        let rest = text_to_split.split_off(chunk_size);
         texts.push(format!(
             "{}{}",
-            text_to_split[..(number_of_characters - 1)].to_owned(),
+            text_to_split.iter().collect::<String>(),
             "…".to_owned()
         ));
-        text_to_split = text_to_split[number_of_characters - 1..].to_owned();
+        text_to_split = rest;
     }
     return texts;
 }
 
+/// The last post of a thread that was cut by [`limit_posts`]
+pub const TRUNCATED_POST: &str = "…(truncated, see link)";
+
+/// Limits `texts` to `max_posts`, replacing the rest with a [`TRUNCATED_POST`]
+///
+/// # Arguments
+///
+/// * `texts` - The texts as returned by [`split_text`]
+/// * `max_posts` - The maximum number of posts, at least 2. Unlimited if `None`
+pub fn limit_posts(mut texts: Vec<String>, max_posts: Option<usize>) -> Vec<String> {
+    match max_posts {
+        Some(max_posts) if texts.len() > max_posts => {
+            texts.truncate(max_posts.saturating_sub(1).max(1));
+            texts.push(TRUNCATED_POST.to_owned());
+            texts
+        }
+        _ => texts,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,4 +127,52 @@ mod tests {
         assert_eq!(result[1], "4567…");
         assert_eq!(result[2], "89");
     }
+
+    #[test]
+    fn test_limit_text_long_word() {
+        let text = "a".repeat(600);
+        let result = split_text(text.clone(), 280, vec![]);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].chars().count(), 280);
+        assert_eq!(result[1].chars().count(), 280);
+        assert_eq!(result[2].chars().count(), 42);
+        assert_eq!(result.concat().replace("…", ""), text);
+    }
+
+    #[test]
+    fn test_limit_text_multi_byte_characters() {
+        let text = "ÆÆÆÆÆÆ".to_owned();
+        let result = split_text(text.clone(), 4, vec![]);
+        assert_eq!(result, vec!["ÆÆÆ…", "ÆÆÆ"]);
+    }
+
+    #[test]
+    fn test_limit_text_rarity_emoji() {
+        let text = "🟠 Ajani🟡".to_owned();
+        let result = split_text(text.clone(), 5, vec![]);
+        assert_eq!(result, vec!["🟠 Aj…", "ani🟡"]);
+    }
+
+    #[test]
+    fn test_limit_text_no_characters_left() {
+        let text = "0123".to_owned();
+        let result = split_text(text.clone(), 5, vec![Additional::Number(10)]);
+        assert_eq!(result, vec!["0…", "1…", "2…", "3"]);
+    }
+
+    #[test]
+    fn test_limit_posts() {
+        let texts = split_text("0123456789".repeat(10), 5, vec![]);
+        assert_eq!(texts.len(), 25);
+
+        let result = limit_posts(texts, Some(3));
+        assert_eq!(result, vec!["0123…", "4567…", TRUNCATED_POST]);
+    }
+
+    #[test]
+    fn test_limit_posts_within_limit() {
+        let texts = split_text("0123456789".to_owned(), 5, vec![]);
+        assert_eq!(limit_posts(texts.clone(), Some(3)), texts);
+        assert_eq!(limit_posts(texts.clone(), None), texts);
+    }
 }